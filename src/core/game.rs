@@ -1,14 +1,170 @@
 use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
 
 #[derive(Component)]
 pub struct Block {
     pub size: Vec2,
     pub settled: bool,
+    pub rest_time: f32, // Seconds the block has been nearly still
 }
 
 #[derive(Component)]
 pub struct Ground;
 
+/// Marks the block the player is placing, until it first touches anything
+#[derive(Component)]
+pub struct ActiveBlock;
+
+/// Player requests for the active block, sent by input systems
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockCommand {
+    Drop,
+    RotateClockwise,
+    RotateCounterClockwise,
+}
+
+/// Sent once when a block comes to rest on the tower
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockSettled {
+    pub block: Entity,
+}
+
+/// Rule set chosen when a run starts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameMode {
+    #[default]
+    Classic,
+    Casual,
+    Challenge,
+}
+
+/// How forgiving a run is
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+/// How tilt input moves the tower
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ControlScheme {
+    /// Tilt swings world gravity around a fixed ground
+    #[default]
+    TiltGravity,
+    /// Gravity stays down and tilt moves the ground like a balance board
+    TiltPlatform,
+    /// Gravity stays down and tilt steers only the block still falling
+    SteerBlock,
+}
+
+impl GameMode {
+    /// Control scheme a run in this mode uses unless the host picks one
+    pub fn default_control_scheme(&self) -> ControlScheme {
+        match self {
+            GameMode::Classic => ControlScheme::TiltGravity,
+            GameMode::Casual => ControlScheme::SteerBlock,
+            GameMode::Challenge => ControlScheme::TiltPlatform,
+        }
+    }
+
+    /// Whether the landing prediction is shown unless the host says otherwise
+    pub fn landing_assist(&self, difficulty: Difficulty) -> bool {
+        match self {
+            GameMode::Casual => difficulty != Difficulty::Hard,
+            GameMode::Classic => difficulty == Difficulty::Easy,
+            GameMode::Challenge => false,
+        }
+    }
+}
+
+/// Settings for the current run
+#[derive(Resource, Debug, Clone, Default)]
+pub struct GameSession {
+    pub mode: GameMode,
+    pub control_scheme: ControlScheme,
+    pub difficulty: Difficulty,
+    pub landing_assist: bool, // Show the landing ghost and trajectory
+    pub seed: u64,            // Seeds block generation so runs can be replayed
+}
+
+/// Shapes a dropped block can take, picked by `BlockGenerator`
+pub const BLOCK_SIZES: [Vec2; 4] = [
+    Vec2::new(60.0, 20.0),
+    Vec2::new(80.0, 20.0),
+    Vec2::new(40.0, 20.0),
+    Vec2::new(40.0, 40.0),
+];
+
+/// Picks the block each drop spawns. Reseeded from `GameSession::seed` whenever a
+/// run starts, so the same seed always gives the same sequence of blocks.
+#[derive(Resource, Debug, Clone)]
+pub struct BlockGenerator {
+    rng: StdRng,
+}
+
+impl Default for BlockGenerator {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl BlockGenerator {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn reseed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// Size of the next block to drop
+    pub fn next_size(&mut self) -> Vec2 {
+        BLOCK_SIZES[self.rng.random_range(0..BLOCK_SIZES.len())]
+    }
+}
+
+/// Why the game is paused
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PauseReason {
+    #[default]
+    Player,
+    Host,
+    /// The page was hidden or the window lost focus
+    FocusLost,
+}
+
+/// Seconds of countdown between a resume request and physics restarting
+pub const RESUME_COUNTDOWN: f32 = 3.0;
+
+/// Details of the current pause, reset whenever the game leaves `GameState::Paused`
+#[derive(Resource, Debug, Clone, Default)]
+pub struct PauseState {
+    pub reason: PauseReason,
+    pub countdown: Option<f32>, // Seconds left before play resumes
+}
+
+impl PauseState {
+    /// Start the resume countdown unless it is already running
+    pub fn request_resume(&mut self) {
+        if self.countdown.is_none() {
+            self.countdown = Some(RESUME_COUNTDOWN);
+        }
+    }
+
+    pub fn cancel_resume(&mut self) {
+        self.countdown = None;
+    }
+}
+
 #[derive(Component)]
 pub struct Tower {
     pub height: f32,
@@ -20,6 +176,28 @@ impl Default for Block {
         Self {
             size: Vec2::new(60.0, 20.0),
             settled: false,
+            rest_time: 0.0,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_gives_the_same_blocks() {
+        let sequence = |generator: &mut BlockGenerator| -> Vec<Vec2> {
+            (0..20).map(|_| generator.next_size()).collect()
+        };
+
+        let mut first = BlockGenerator::new(42);
+        let mut second = BlockGenerator::new(42);
+        let expected = sequence(&mut first);
+        assert_eq!(sequence(&mut second), expected);
+
+        // Restarting a run replays it from the top
+        first.reseed(42);
+        assert_eq!(sequence(&mut first), expected);
+    }
+}
@@ -1,5 +1,6 @@
 pub mod game;
 pub mod scoring;
+pub mod storage;
 
 pub use game::*;
 pub use scoring::*;
@@ -1,3 +1,6 @@
+use super::BlockSettled;
+use crate::physics::StabilityMargin;
+use crate::GameScore;
 use bevy::prelude::*;
 
 #[derive(Resource, Default)]
@@ -5,6 +8,8 @@ pub struct ScoreSystem {
     pub perfect_stack_threshold: f32,
     pub perfect_stack_bonus: u32,
     pub base_points: u32,
+    pub risky_margin_ratio: f32, // Stability ratio below which a standing tower counts as risky
+    pub risky_build_bonus: u32,
 }
 
 impl ScoreSystem {
@@ -13,6 +18,8 @@ impl ScoreSystem {
             perfect_stack_threshold: 3.0, // pixels
             perfect_stack_bonus: 2,
             base_points: 1,
+            risky_margin_ratio: 0.25,
+            risky_build_bonus: 3,
         }
     }
 
@@ -23,4 +30,117 @@ impl ScoreSystem {
             self.base_points
         }
     }
+
+    /// Bonus for a tower that leans close to its support edge but still stands,
+    /// given the weakest level's `LevelStability::ratio`
+    pub fn calculate_stability_bonus(&self, margin_ratio: f32) -> u32 {
+        if (0.0..self.risky_margin_ratio).contains(&margin_ratio) {
+            self.risky_build_bonus
+        } else {
+            0
+        }
+    }
+}
+
+/// System paying the risky-build bonus when a block settles onto a tower that
+/// leans close to its support edge but still stands
+pub fn award_stability_bonus(
+    mut settled: EventReader<BlockSettled>,
+    stability: Res<StabilityMargin>,
+    scoring: Res<ScoreSystem>,
+    mut score: ResMut<GameScore>,
+) {
+    // Blocks settling in the same frame share one check of the tower
+    if settled.read().count() == 0 {
+        return;
+    }
+    let Some(weakest) = stability.weakest else {
+        return;
+    };
+
+    let bonus = scoring.calculate_stability_bonus(weakest.ratio());
+    if bonus > 0 {
+        score.current += bonus;
+        score.best = score.best.max(score.current);
+        debug!("Risky build bonus: +{}", bonus);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::LevelStability;
+
+    /// Weakest level with 30px of support either side of centre
+    fn stability_with_margin(margin: f32) -> StabilityMargin {
+        let level = LevelStability {
+            block: Entity::PLACEHOLDER,
+            height: 0.0,
+            load: 1.0,
+            center_of_mass: Vec2::ZERO,
+            support: (-30.0, 30.0),
+            margin,
+        };
+        StabilityMargin {
+            levels: vec![level],
+            weakest: Some(level),
+        }
+    }
+
+    #[test]
+    fn stability_bonus_only_for_risky_but_standing_towers() {
+        let scoring = ScoreSystem::new();
+
+        assert_eq!(
+            scoring.calculate_stability_bonus(0.0),
+            scoring.risky_build_bonus
+        );
+        assert_eq!(
+            scoring.calculate_stability_bonus(0.2),
+            scoring.risky_build_bonus
+        );
+        assert_eq!(scoring.calculate_stability_bonus(0.25), 0);
+        assert_eq!(scoring.calculate_stability_bonus(0.8), 0);
+        assert_eq!(scoring.calculate_stability_bonus(-0.1), 0);
+    }
+
+    fn scoring_app(stability: StabilityMargin) -> App {
+        let mut app = App::new();
+        app.add_event::<BlockSettled>()
+            .insert_resource(stability)
+            .insert_resource(ScoreSystem::new())
+            .init_resource::<GameScore>()
+            .add_systems(Update, award_stability_bonus);
+        app
+    }
+
+    fn settle(app: &mut App) {
+        app.world_mut().send_event(BlockSettled {
+            block: Entity::PLACEHOLDER,
+        });
+        app.update();
+    }
+
+    #[test]
+    fn settling_on_a_risky_tower_pays_the_bonus() {
+        // 5px of 30px half-width is a ratio of about 0.17
+        let mut app = scoring_app(stability_with_margin(5.0));
+
+        app.update();
+        assert_eq!(app.world().resource::<GameScore>().current, 0);
+
+        settle(&mut app);
+        let score = app.world().resource::<GameScore>();
+        assert_eq!(score.current, 3);
+        assert_eq!(score.best, 3);
+    }
+
+    #[test]
+    fn settling_on_a_steady_or_toppling_tower_pays_nothing() {
+        for margin in [20.0, -2.0] {
+            let mut app = scoring_app(stability_with_margin(margin));
+            settle(&mut app);
+            assert_eq!(app.world().resource::<GameScore>().current, 0);
+        }
+    }
 }
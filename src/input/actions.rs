@@ -0,0 +1,450 @@
+use super::{
+    ActiveGamepad, ControlLayout, HostEnvironment, InputSource, TiltInput, TouchControlLayout,
+    TouchZone,
+};
+use crate::core::storage;
+use bevy::input::touch::Touch;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Named player actions, resolved from keyboard, gamepad and touch bindings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Action {
+    TiltLeft,
+    TiltRight,
+    TiltForward,
+    TiltBack,
+    Drop,
+    RotateClockwise,
+    RotateCounterClockwise,
+    Pause,
+    Calibrate,
+    CycleInputSource,
+    ToggleSensorMode,
+    ToggleFilterMode,
+    SensitivityLow,
+    SensitivityNormal,
+    SensitivityHigh,
+    SensitivityMax,
+    DeadZoneUp,
+    DeadZoneDown,
+    ShowInputInfo,
+    ToggleDiagnostics,
+    CycleGravityProfile,
+    ToggleControlsMenu,
+}
+
+impl Action {
+    pub const ALL: [Action; 22] = [
+        Action::TiltLeft,
+        Action::TiltRight,
+        Action::TiltForward,
+        Action::TiltBack,
+        Action::Drop,
+        Action::RotateClockwise,
+        Action::RotateCounterClockwise,
+        Action::Pause,
+        Action::Calibrate,
+        Action::CycleInputSource,
+        Action::ToggleSensorMode,
+        Action::ToggleFilterMode,
+        Action::SensitivityLow,
+        Action::SensitivityNormal,
+        Action::SensitivityHigh,
+        Action::SensitivityMax,
+        Action::DeadZoneUp,
+        Action::DeadZoneDown,
+        Action::ShowInputInfo,
+        Action::ToggleDiagnostics,
+        Action::CycleGravityProfile,
+        Action::ToggleControlsMenu,
+    ];
+
+    /// Human-readable name for menus
+    pub fn label(&self) -> &'static str {
+        match self {
+            Action::TiltLeft => "Tilt left",
+            Action::TiltRight => "Tilt right",
+            Action::TiltForward => "Tilt forward",
+            Action::TiltBack => "Tilt back",
+            Action::Drop => "Drop",
+            Action::RotateClockwise => "Rotate clockwise",
+            Action::RotateCounterClockwise => "Rotate counter-clockwise",
+            Action::Pause => "Pause",
+            Action::Calibrate => "Calibrate",
+            Action::CycleInputSource => "Cycle input source",
+            Action::ToggleSensorMode => "Toggle sensor mode",
+            Action::ToggleFilterMode => "Toggle filter mode",
+            Action::SensitivityLow => "Sensitivity 0.5x",
+            Action::SensitivityNormal => "Sensitivity 1.0x",
+            Action::SensitivityHigh => "Sensitivity 1.5x",
+            Action::SensitivityMax => "Sensitivity 2.0x",
+            Action::DeadZoneUp => "Increase dead zone",
+            Action::DeadZoneDown => "Decrease dead zone",
+            Action::ShowInputInfo => "Show input info",
+            Action::ToggleDiagnostics => "Sensor diagnostics",
+            Action::CycleGravityProfile => "Cycle gravity profile",
+            Action::ToggleControlsMenu => "Controls menu",
+        }
+    }
+}
+
+/// A physical input that can trigger an action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Binding {
+    Key(KeyCode),
+    GamepadButton(GamepadButtonType),
+    Touch(TouchZone),
+}
+
+impl Binding {
+    /// Human-readable name for menus
+    pub fn label(&self) -> String {
+        match self {
+            Binding::Key(key) => {
+                let name = format!("{:?}", key);
+                name.strip_prefix("Key")
+                    .or_else(|| name.strip_prefix("Digit"))
+                    .unwrap_or(&name)
+                    .to_string()
+            }
+            Binding::GamepadButton(button) => format!("Pad {:?}", button),
+            Binding::Touch(zone) => format!("Touch {:?}", zone),
+        }
+    }
+
+    fn same_device(&self, other: &Binding) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+/// Storage key for persisted bindings
+const BINDINGS_STORAGE_KEY: &str = "bindings";
+
+/// Action to binding table, rebindable at runtime and persisted between sessions
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionBindings {
+    bindings: HashMap<Action, Vec<Binding>>,
+}
+
+impl Default for ActionBindings {
+    fn default() -> Self {
+        use Binding::{GamepadButton as Pad, Key, Touch};
+
+        let bindings = HashMap::from([
+            (
+                Action::TiltLeft,
+                vec![Key(KeyCode::ArrowLeft), Key(KeyCode::KeyA)],
+            ),
+            (
+                Action::TiltRight,
+                vec![Key(KeyCode::ArrowRight), Key(KeyCode::KeyD)],
+            ),
+            (
+                Action::TiltForward,
+                vec![Key(KeyCode::ArrowUp), Key(KeyCode::KeyW)],
+            ),
+            (
+                Action::TiltBack,
+                vec![Key(KeyCode::ArrowDown), Key(KeyCode::KeyS)],
+            ),
+            (
+                Action::Drop,
+                vec![
+                    Key(KeyCode::Space),
+                    Pad(GamepadButtonType::South),
+                    Touch(TouchZone::DropArea),
+                ],
+            ),
+            (
+                Action::RotateClockwise,
+                vec![Key(KeyCode::KeyE), Pad(GamepadButtonType::RightTrigger)],
+            ),
+            (
+                Action::RotateCounterClockwise,
+                vec![Key(KeyCode::KeyQ), Pad(GamepadButtonType::LeftTrigger)],
+            ),
+            (
+                Action::Pause,
+                vec![Key(KeyCode::Escape), Pad(GamepadButtonType::Start)],
+            ),
+            (
+                Action::Calibrate,
+                vec![Key(KeyCode::KeyC), Pad(GamepadButtonType::Select)],
+            ),
+            (Action::CycleInputSource, vec![Key(KeyCode::KeyT)]),
+            (Action::ToggleSensorMode, vec![Key(KeyCode::KeyM)]),
+            (Action::ToggleFilterMode, vec![Key(KeyCode::KeyF)]),
+            (Action::SensitivityLow, vec![Key(KeyCode::Digit1)]),
+            (Action::SensitivityNormal, vec![Key(KeyCode::Digit2)]),
+            (Action::SensitivityHigh, vec![Key(KeyCode::Digit3)]),
+            (Action::SensitivityMax, vec![Key(KeyCode::Digit4)]),
+            (Action::DeadZoneUp, vec![Key(KeyCode::Equal)]),
+            (Action::DeadZoneDown, vec![Key(KeyCode::Minus)]),
+            (Action::ShowInputInfo, vec![Key(KeyCode::KeyI)]),
+            (Action::ToggleDiagnostics, vec![Key(KeyCode::F3)]),
+            (Action::CycleGravityProfile, vec![Key(KeyCode::KeyG)]),
+            (Action::ToggleControlsMenu, vec![Key(KeyCode::F1)]),
+        ]);
+
+        Self { bindings }
+    }
+}
+
+impl ActionBindings {
+    /// Get bindings for an action
+    pub fn bindings_for(&self, action: Action) -> &[Binding] {
+        self.bindings.get(&action).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Add a binding to an action, removing it from any other action so bindings never collide
+    pub fn add_binding(&mut self, action: Action, binding: Binding) {
+        self.remove_from_all(binding);
+        self.bindings.entry(action).or_default().push(binding);
+    }
+
+    /// Replace an action's bindings for the same device (keyboard, gamepad or touch)
+    pub fn rebind(&mut self, action: Action, binding: Binding) {
+        self.remove_from_all(binding);
+        let bindings = self.bindings.entry(action).or_default();
+        bindings.retain(|existing| !existing.same_device(&binding));
+        bindings.push(binding);
+        info!("Rebound {:?} to {}", action, binding.label());
+    }
+
+    /// Remove a binding from an action
+    pub fn remove_binding(&mut self, action: Action, binding: Binding) {
+        if let Some(bindings) = self.bindings.get_mut(&action) {
+            bindings.retain(|existing| *existing != binding);
+        }
+    }
+
+    /// Restore the default bindings
+    pub fn reset_to_defaults(&mut self) {
+        *self = Self::default();
+        info!("Bindings reset to defaults");
+    }
+
+    fn remove_from_all(&mut self, binding: Binding) {
+        for bindings in self.bindings.values_mut() {
+            bindings.retain(|existing| *existing != binding);
+        }
+    }
+
+    /// Load persisted bindings on top of the defaults, so new actions keep their default keys
+    pub fn load() -> Self {
+        let mut bindings = Self::default();
+
+        if let Some(saved) = storage::load(BINDINGS_STORAGE_KEY) {
+            match serde_json::from_str::<ActionBindings>(&saved) {
+                Ok(saved) => bindings.merge_saved(saved),
+                Err(err) => warn!("Ignoring saved bindings: {}", err),
+            }
+        }
+
+        bindings
+    }
+
+    /// Replace the bindings of every saved action. A binding the player assigned
+    /// also comes off any default that uses it, so one key never fires two actions.
+    fn merge_saved(&mut self, saved: ActionBindings) {
+        for binding in saved.bindings.values().flatten() {
+            self.remove_from_all(*binding);
+        }
+        self.bindings.extend(saved.bindings);
+    }
+
+    /// Persist bindings
+    pub fn save(&self) {
+        let result = serde_json::to_string(self)
+            .map_err(|err| err.to_string())
+            .and_then(|json| storage::save(BINDINGS_STORAGE_KEY, &json));
+
+        if let Err(err) = result {
+            warn!("Failed to save bindings: {}", err);
+        }
+    }
+}
+
+/// Per-frame action state resolved from the current bindings
+#[derive(Resource, Default, Debug)]
+pub struct ActionState {
+    pressed: HashSet<Action>,
+    just_pressed: HashSet<Action>,
+    just_released: HashSet<Action>,
+}
+
+impl ActionState {
+    pub fn pressed(&self, action: Action) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.just_pressed.contains(&action)
+    }
+
+    pub fn just_released(&self, action: Action) -> bool {
+        self.just_released.contains(&action)
+    }
+
+    fn clear(&mut self) {
+        self.pressed.clear();
+        self.just_pressed.clear();
+        self.just_released.clear();
+    }
+}
+
+/// Action currently waiting for a new binding from the player
+#[derive(Resource, Default, Debug)]
+pub struct RebindCapture {
+    pub action: Option<Action>,
+}
+
+impl RebindCapture {
+    pub fn begin(&mut self, action: Action) {
+        self.action = Some(action);
+        info!("Press a key or button for {:?} (Escape cancels)", action);
+    }
+
+    pub fn cancel(&mut self) {
+        self.action = None;
+    }
+}
+
+/// System for loading persisted bindings at startup
+pub fn load_action_bindings(mut bindings: ResMut<ActionBindings>) {
+    *bindings = ActionBindings::load();
+}
+
+/// System for resolving bindings into action state
+#[allow(clippy::too_many_arguments)]
+pub fn update_action_state(
+    bindings: Res<ActionBindings>,
+    capture: Res<RebindCapture>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    active_gamepad: Res<ActiveGamepad>,
+    touches: Res<Touches>,
+    touch_layout: Res<TouchControlLayout>,
+    tilt_input: Res<TiltInput>,
+    environment: Res<HostEnvironment>,
+    mut action_state: ResMut<ActionState>,
+) {
+    action_state.clear();
+
+    // Inputs go to the rebind capture instead of triggering actions
+    if capture.action.is_some() {
+        return;
+    }
+
+    // Touch zones only exist while the on-screen controls are shown
+    let touch_enabled = tilt_input.input_source == InputSource::Virtual
+        || environment.layout == ControlLayout::Touch;
+    let touch_zone = |touch: &&Touch| touch_layout.zone_at(touch.start_position());
+
+    for action in Action::ALL {
+        for binding in bindings.bindings_for(action) {
+            let (pressed, just_pressed, just_released) = match *binding {
+                Binding::Key(key) => (
+                    keyboard_input.pressed(key),
+                    keyboard_input.just_pressed(key),
+                    keyboard_input.just_released(key),
+                ),
+                Binding::GamepadButton(button_type) => match active_gamepad.gamepad {
+                    Some(gamepad) => {
+                        let button = GamepadButton::new(gamepad, button_type);
+                        (
+                            gamepad_buttons.pressed(button),
+                            gamepad_buttons.just_pressed(button),
+                            gamepad_buttons.just_released(button),
+                        )
+                    }
+                    None => (false, false, false),
+                },
+                Binding::Touch(zone) if touch_enabled => (
+                    touches.iter().any(|t| touch_zone(&t) == Some(zone)),
+                    touches
+                        .iter_just_pressed()
+                        .any(|t| touch_zone(&t) == Some(zone)),
+                    touches
+                        .iter_just_released()
+                        .any(|t| touch_zone(&t) == Some(zone)),
+                ),
+                Binding::Touch(_) => (false, false, false),
+            };
+
+            if pressed {
+                action_state.pressed.insert(action);
+            }
+            if just_pressed {
+                action_state.just_pressed.insert(action);
+            }
+            if just_released {
+                action_state.just_released.insert(action);
+            }
+        }
+    }
+}
+
+/// System for capturing the next key or gamepad button as a new binding
+pub fn capture_rebind(
+    mut capture: ResMut<RebindCapture>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    mut bindings: ResMut<ActionBindings>,
+) {
+    let Some(action) = capture.action else {
+        return;
+    };
+
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        capture.cancel();
+        return;
+    }
+
+    let binding = keyboard_input
+        .get_just_pressed()
+        .next()
+        .map(|key| Binding::Key(*key))
+        .or_else(|| {
+            gamepad_buttons
+                .get_just_pressed()
+                .next()
+                .map(|button| Binding::GamepadButton(button.button_type))
+        });
+
+    if let Some(binding) = binding {
+        bindings.rebind(action, binding);
+        bindings.save();
+        capture.cancel();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saved_key_is_dropped_from_other_default_actions() {
+        // Saved before CycleGravityProfile existed, with G taken for Calibrate
+        let saved = ActionBindings {
+            bindings: HashMap::from([(Action::Calibrate, vec![Binding::Key(KeyCode::KeyG)])]),
+        };
+
+        let mut bindings = ActionBindings::default();
+        bindings.merge_saved(saved);
+
+        assert_eq!(
+            bindings.bindings_for(Action::Calibrate),
+            [Binding::Key(KeyCode::KeyG)]
+        );
+        assert!(bindings
+            .bindings_for(Action::CycleGravityProfile)
+            .is_empty());
+        // Actions the save didn't mention keep their defaults
+        assert!(bindings
+            .bindings_for(Action::Drop)
+            .contains(&Binding::Key(KeyCode::Space)));
+    }
+}
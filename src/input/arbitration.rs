@@ -0,0 +1,187 @@
+use super::{Action, ActionState, ActiveGamepad, GamepadTiltSettings, InputSource, TiltInput};
+use bevy::input::mouse::MouseMotion;
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// Why the active input source changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceChangeReason {
+    /// The player or host page picked a source
+    Selected,
+    /// The preferred source stopped producing data
+    Stale,
+    /// The preferred source started producing data again
+    Resumed,
+}
+
+/// Sent whenever the active input source changes, for UI notices
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct InputSourceChanged {
+    pub from: InputSource,
+    pub to: InputSource,
+    pub reason: SourceChangeReason,
+}
+
+/// Chooses the active input source, falling back when the preferred source goes quiet
+#[derive(Resource)]
+pub struct InputArbiter {
+    pub preferred: InputSource,
+    pub fallback: InputSource,
+    pub stale_timeout: f64, // Milliseconds without data before a streaming source is stale
+    last_activity: HashMap<InputSource, f64>,
+    applied_source: InputSource,
+    falling_back: bool,
+}
+
+impl Default for InputArbiter {
+    fn default() -> Self {
+        Self {
+            preferred: InputSource::Device,
+            fallback: InputSource::Keyboard,
+            stale_timeout: 1500.0,
+            last_activity: HashMap::new(),
+            applied_source: InputSource::Device,
+            falling_back: false,
+        }
+    }
+}
+
+impl InputArbiter {
+    /// Record that a source produced input at `now` (milliseconds of game time)
+    pub fn record_activity(&mut self, source: InputSource, now: f64) {
+        self.last_activity.insert(source, now);
+    }
+
+    /// Get time of the last input from a source
+    pub fn last_activity(&self, source: InputSource) -> Option<f64> {
+        self.last_activity.get(&source).copied()
+    }
+
+    /// Whether a source has produced input within the staleness timeout
+    pub fn is_fresh(&self, source: InputSource, now: f64) -> bool {
+        self.last_activity(source)
+            .is_some_and(|last| now - last < self.stale_timeout)
+    }
+
+    /// Whether the arbiter has switched away from the preferred source
+    pub fn is_falling_back(&self) -> bool {
+        self.falling_back
+    }
+
+    /// Pick the most recently used fresh source other than the preferred one
+    fn choose_fallback(&self, now: f64) -> InputSource {
+        self.last_activity
+            .iter()
+            .filter(|(source, _)| **source != self.preferred && self.is_fresh(**source, now))
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(source, _)| *source)
+            .unwrap_or(self.fallback)
+    }
+}
+
+impl InputSource {
+    /// Sources that report continuously while working, so silence means failure.
+    /// Idle keyboards, mice and sticks are silent by design.
+    pub fn is_streaming(&self) -> bool {
+        matches!(self, InputSource::Device)
+    }
+}
+
+/// System for recording activity of locally polled input sources.
+/// Device activity is recorded as bridge events are processed.
+#[allow(clippy::too_many_arguments)]
+pub fn track_input_activity(
+    actions: Res<ActionState>,
+    touches: Res<Touches>,
+    mut mouse_motion: EventReader<MouseMotion>,
+    active_gamepad: Res<ActiveGamepad>,
+    gamepad_settings: Res<GamepadTiltSettings>,
+    axes: Res<Axis<GamepadAxis>>,
+    mut arbiter: ResMut<InputArbiter>,
+    time: Res<Time>,
+) {
+    let now = time.elapsed_seconds_f64() * 1000.0;
+
+    let tilt_actions = [
+        Action::TiltLeft,
+        Action::TiltRight,
+        Action::TiltForward,
+        Action::TiltBack,
+    ];
+    if tilt_actions.iter().any(|action| actions.pressed(*action)) {
+        arbiter.record_activity(InputSource::Keyboard, now);
+    }
+
+    if touches.iter().next().is_some() {
+        arbiter.record_activity(InputSource::Virtual, now);
+    }
+
+    if mouse_motion.read().count() > 0 {
+        arbiter.record_activity(InputSource::Mouse, now);
+    }
+
+    if let Some(gamepad) = active_gamepad.gamepad {
+        let stick = Vec2::new(
+            axes.get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX))
+                .unwrap_or(0.0),
+            axes.get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickY))
+                .unwrap_or(0.0),
+        );
+        if stick.length() > gamepad_settings.dead_zone {
+            arbiter.record_activity(InputSource::Gamepad, now);
+        }
+    }
+}
+
+/// System for switching to a fallback source when the preferred one goes stale,
+/// and back again when it resumes
+pub fn arbitrate_input_source(
+    mut arbiter: ResMut<InputArbiter>,
+    mut tilt_input: ResMut<TiltInput>,
+    mut source_changes: EventWriter<InputSourceChanged>,
+    time: Res<Time>,
+) {
+    let now = time.elapsed_seconds_f64() * 1000.0;
+    let current = tilt_input.input_source;
+
+    // Any change made elsewhere (key, gamepad connect, host page) becomes the new preference
+    if current != arbiter.applied_source {
+        source_changes.send(InputSourceChanged {
+            from: arbiter.applied_source,
+            to: current,
+            reason: SourceChangeReason::Selected,
+        });
+        arbiter.preferred = current;
+        arbiter.applied_source = current;
+        arbiter.falling_back = false;
+        return;
+    }
+
+    let preferred = arbiter.preferred;
+    let (target, reason) = if !arbiter.falling_back {
+        if !preferred.is_streaming() || arbiter.is_fresh(preferred, now) {
+            return;
+        }
+        (arbiter.choose_fallback(now), SourceChangeReason::Stale)
+    } else {
+        if !arbiter.is_fresh(preferred, now) {
+            return;
+        }
+        (preferred, SourceChangeReason::Resumed)
+    };
+
+    if target != current {
+        info!("Input source {:?} -> {:?} ({:?})", current, target, reason);
+        // Drop the stale reading so gravity doesn't stay skewed
+        tilt_input.reset_tilt();
+        tilt_input.set_input_source(target);
+        source_changes.send(InputSourceChanged {
+            from: current,
+            to: target,
+            reason,
+        });
+    }
+
+    arbiter.applied_source = target;
+    arbiter.falling_back = reason == SourceChangeReason::Stale;
+}
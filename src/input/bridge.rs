@@ -1,6 +1,10 @@
+use super::{
+    BatchReport, GameCapabilities, GameSnapshot, HostCapabilities, InputSource, StreamStats,
+};
+use crate::core::{ControlScheme, Difficulty, GameMode};
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 use std::sync::{Arc, Mutex};
 use wasm_bindgen::prelude::*;
 
@@ -12,6 +16,34 @@ pub struct DeviceOrientationData {
     pub timestamp: f64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccelerationData {
+    pub x: f32, // m/s², towards the right edge of the device
+    pub y: f32, // m/s², towards the top edge of the device
+    pub z: f32, // m/s², out of the screen
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RotationRateData {
+    pub alpha: f32, // degrees per second around Z
+    pub beta: f32,  // degrees per second around X
+    pub gamma: f32, // degrees per second around Y
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceMotionData {
+    pub acceleration_including_gravity: AccelerationData,
+    pub rotation_rate: RotationRateData,
+    pub interval: f64, // Sampling interval in milliseconds
+    pub timestamp: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreenOrientationData {
+    pub angle: i32, // screen.orientation.angle (0, 90, 180, 270)
+    pub timestamp: f64,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PermissionStatus {
     pub granted: bool,
@@ -19,23 +51,172 @@ pub struct PermissionStatus {
     pub available: bool,
 }
 
+/// Remote control commands the host page can send, e.g. from HTML buttons
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum HostCommand {
+    Start {
+        #[serde(default)]
+        mode: GameMode,
+        #[serde(default)]
+        seed: Option<u64>, // Random when omitted
+        #[serde(default)]
+        scheme: Option<ControlScheme>, // The mode's default when omitted
+        #[serde(default)]
+        difficulty: Difficulty,
+        #[serde(default)]
+        assist: Option<bool>, // Landing prediction; the mode's default when omitted
+    },
+    Pause,
+    Resume,
+    Restart,
+    QuitToMenu,
+    SetInputSource {
+        source: InputSource,
+    },
+    Calibrate,
+    SetGravityProfile {
+        profile: String, // Preset name, e.g. "gentle"
+    },
+}
+
+/// A host command tagged with the id echoed back in its `CommandResult`
+#[derive(Event, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HostCommandRequest {
+    pub id: u64,
+    #[serde(flatten)]
+    pub command: HostCommand,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum BridgeEvent {
     DeviceOrientation(DeviceOrientationData),
+    DeviceMotion(DeviceMotionData),
+    ScreenOrientation(ScreenOrientationData),
     PermissionStatusChanged(PermissionStatus),
-    GameStateRequest { state: String },
-    GameStateResponse { state: String, data: String },
+    HostCommand(HostCommandRequest),
+    Handshake(HostCapabilities),
+    /// `document.visibilitychange`
+    VisibilityChanged {
+        visible: bool,
+    },
+    /// `window.pagehide`; the page may be frozen or discarded next
+    PageHide {
+        persisted: bool,
+    },
+    /// `window` blur/focus
+    FocusChanged {
+        focused: bool,
+    },
 }
 
-// Global bridge instance for communication between JS and Rust
-lazy_static::lazy_static! {
-    static ref BRIDGE: Arc<Mutex<EventBridge>> = Arc::new(Mutex::new(EventBridge::new()));
+/// Events sent from the game to the host page
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum OutboundEvent {
+    ScoreChanged {
+        current: u32,
+        best: u32,
+    },
+    StateChanged {
+        state: String,
+    },
+    Haptic {
+        duration_ms: u32,
+    },
+    AudioCue {
+        cue: String,
+    },
+    GameStateResponse {
+        state: String,
+        data: String,
+    },
+    HandshakeAck(GameCapabilities),
+    CommandResult {
+        id: u64,
+        ok: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+}
+
+/// Outbound event numbered so the host page can detect dropped messages
+#[derive(Debug, Clone, Serialize)]
+pub struct OutboundMessage {
+    pub seq: u64,
+    #[serde(flatten)]
+    pub event: OutboundEvent,
+}
+
+/// Batch handed to JS on each drain
+#[derive(Debug, Clone, Serialize)]
+pub struct OutboundBatch {
+    pub version: u32,
+    pub dropped: u64, // Messages lost to overflow since the previous drain
+    pub events: Vec<OutboundMessage>,
+}
+
+/// Version of the outbound batch format
+pub const OUTBOUND_FORMAT_VERSION: u32 = 1;
+
+/// How consecutive orientation samples are merged while waiting in the queue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CoalesceMode {
+    /// Keep every sample
+    None,
+    /// Keep only the newest sample
+    Latest,
+    /// Merge samples into their running average
+    Average,
+}
+
+/// Backpressure settings for the inbound event queue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueuePolicy {
+    pub capacity: usize, // Sensor samples beyond this drop the oldest sample
+    pub coalesce: CoalesceMode,
+}
+
+impl Default for QueuePolicy {
+    fn default() -> Self {
+        Self {
+            capacity: 120, // ~2 seconds at 60Hz
+            coalesce: CoalesceMode::Latest,
+        }
+    }
+}
+
+impl BridgeEvent {
+    /// Sensor samples can be merged or dropped under pressure; everything else must arrive
+    pub fn is_sensor_sample(&self) -> bool {
+        matches!(
+            self,
+            BridgeEvent::DeviceOrientation(_) | BridgeEvent::DeviceMotion(_)
+        )
+    }
+}
+
+/// Move `mean` towards `sample` by `1 / count`, taking the short way around the circle
+fn average_angle(mean: f32, sample: f32, count: u32) -> f32 {
+    let delta = (sample - mean + 180.0).rem_euclid(360.0) - 180.0;
+    mean + delta / count as f32
 }
 
 pub struct EventBridge {
     event_queue: VecDeque<BridgeEvent>,
-    max_queue_size: usize,
+    policy: QueuePolicy,
+    dropped_events: u64,
+    coalesced_events: u64,
+    averaged_samples: u32, // Samples merged into the newest queued orientation event
+    orientation_stats: StreamStats, // Measured on arrival, before any coalescing
+    motion_stats: StreamStats,
+    outbound_queue: VecDeque<OutboundMessage>,
+    max_outbound_size: usize,
+    next_outbound_seq: u64,
+    dropped_outbound: u64,
+    snapshot: Option<GameSnapshot>,
     permission_status: PermissionStatus,
 }
 
@@ -43,7 +224,17 @@ impl EventBridge {
     fn new() -> Self {
         Self {
             event_queue: VecDeque::new(),
-            max_queue_size: 120, // ~2 seconds at 60Hz
+            policy: QueuePolicy::default(),
+            dropped_events: 0,
+            coalesced_events: 0,
+            averaged_samples: 1,
+            orientation_stats: StreamStats::default(),
+            motion_stats: StreamStats::default(),
+            outbound_queue: VecDeque::new(),
+            max_outbound_size: 64, // Host page drains every animation frame
+            next_outbound_seq: 0,
+            dropped_outbound: 0,
+            snapshot: None,
             permission_status: PermissionStatus {
                 granted: false,
                 requested: false,
@@ -53,16 +244,136 @@ impl EventBridge {
     }
 
     pub fn push_event(&mut self, event: BridgeEvent) {
-        if self.event_queue.len() >= self.max_queue_size {
-            self.event_queue.pop_front();
+        match &event {
+            BridgeEvent::DeviceOrientation(data) => self.orientation_stats.record(data.timestamp),
+            BridgeEvent::DeviceMotion(data) => self.motion_stats.record(data.timestamp),
+            _ => {}
+        }
+
+        if let BridgeEvent::DeviceOrientation(data) = &event {
+            if self.coalesce_orientation(data) {
+                self.coalesced_events += 1;
+                return;
+            }
+            self.averaged_samples = 1;
+        }
+
+        if event.is_sensor_sample() && self.sensor_sample_count() >= self.policy.capacity {
+            // Control events are never dropped, so evict the oldest sensor sample
+            if let Some(index) = self.event_queue.iter().position(|e| e.is_sensor_sample()) {
+                self.event_queue.remove(index);
+                self.dropped_events += 1;
+            }
         }
         self.event_queue.push_back(event);
     }
 
+    /// Merge an orientation sample into the tail of the queue when that is also an
+    /// orientation sample, so the order against motion and control events is kept.
+    /// Returns false when the sample must be queued on its own.
+    fn coalesce_orientation(&mut self, data: &DeviceOrientationData) -> bool {
+        if self.policy.coalesce == CoalesceMode::None {
+            return false;
+        }
+
+        let Some(BridgeEvent::DeviceOrientation(queued)) = self.event_queue.back_mut() else {
+            return false;
+        };
+
+        match self.policy.coalesce {
+            CoalesceMode::Latest => *queued = data.clone(),
+            CoalesceMode::Average => {
+                self.averaged_samples += 1;
+                let count = self.averaged_samples;
+                queued.alpha = average_angle(queued.alpha, data.alpha, count);
+                queued.beta = average_angle(queued.beta, data.beta, count);
+                queued.gamma = average_angle(queued.gamma, data.gamma, count);
+                queued.timestamp = data.timestamp;
+            }
+            CoalesceMode::None => unreachable!(),
+        }
+        true
+    }
+
+    fn sensor_sample_count(&self) -> usize {
+        self.event_queue
+            .iter()
+            .filter(|e| e.is_sensor_sample())
+            .count()
+    }
+
+    /// Change capacity and coalescing; an over-full queue is trimmed on the next push
+    pub fn set_queue_policy(&mut self, policy: QueuePolicy) {
+        self.policy = policy;
+    }
+
+    pub fn queue_policy(&self) -> QueuePolicy {
+        self.policy
+    }
+
+    /// Number of orientation samples merged into an already queued one
+    pub fn coalesced_events(&self) -> u64 {
+        self.coalesced_events
+    }
+
+    /// Arrival statistics for orientation and motion samples, taken as they are pushed
+    pub fn stream_stats(&self) -> (&StreamStats, &StreamStats) {
+        (&self.orientation_stats, &self.motion_stats)
+    }
+
+    /// Queue parsed batch events in order
+    pub fn push_events(&mut self, events: Vec<BridgeEvent>) {
+        for event in events {
+            self.push_event(event);
+        }
+    }
+
     pub fn pop_event(&mut self) -> Option<BridgeEvent> {
         self.event_queue.pop_front()
     }
 
+    /// Number of sensor samples dropped because the queue was full
+    pub fn dropped_events(&self) -> u64 {
+        self.dropped_events
+    }
+
+    /// Queue an event for the host page, dropping the oldest when full
+    pub fn push_outbound(&mut self, event: OutboundEvent) {
+        if self.outbound_queue.len() >= self.max_outbound_size {
+            self.outbound_queue.pop_front();
+            self.dropped_outbound += 1;
+        }
+        self.outbound_queue.push_back(OutboundMessage {
+            seq: self.next_outbound_seq,
+            event,
+        });
+        self.next_outbound_seq += 1;
+    }
+
+    /// Take all queued outbound events
+    pub fn drain_outbound(&mut self) -> OutboundBatch {
+        OutboundBatch {
+            version: OUTBOUND_FORMAT_VERSION,
+            dropped: std::mem::take(&mut self.dropped_outbound),
+            events: self.outbound_queue.drain(..).collect(),
+        }
+    }
+
+    /// Replace the snapshot served to synchronous queries
+    pub fn set_snapshot(&mut self, snapshot: GameSnapshot) {
+        self.snapshot = Some(snapshot);
+    }
+
+    /// Serialize a view of the latest snapshot, or a JSON error object
+    pub fn query_view(&self, view: &str) -> String {
+        let result = match &self.snapshot {
+            Some(snapshot) => snapshot.view_json(view),
+            None => Err("game state not published yet".to_string()),
+        };
+
+        result.unwrap_or_else(|error| serde_json::json!({ "error": error }).to_string())
+    }
+
     pub fn get_permission_status(&self) -> &PermissionStatus {
         &self.permission_status
     }
@@ -73,61 +384,406 @@ impl EventBridge {
     }
 }
 
-// WASM-bindgen interface functions for JavaScript interaction
+/// Shared handle to one game's event queues. Each `App` owns its own; on wasm the
+/// handle is also registered under the game's canvas id so page scripts can find it.
 #[wasm_bindgen]
-pub fn js_push_device_orientation(alpha: f32, beta: f32, gamma: f32, timestamp: f64) {
-    let data = DeviceOrientationData {
-        alpha,
-        beta,
-        gamma,
-        timestamp,
-    };
+#[derive(Resource, Clone)]
+pub struct BridgeHandle {
+    inner: Arc<Mutex<EventBridge>>,
+}
+
+/// Canvas id of the page's main game
+pub const DEFAULT_CANVAS_ID: &str = "bevy";
 
-    if let Ok(mut bridge) = BRIDGE.lock() {
-        bridge.push_event(BridgeEvent::DeviceOrientation(data));
+// Handles reachable from JavaScript, keyed by canvas id
+lazy_static::lazy_static! {
+    static ref BRIDGES: Mutex<HashMap<String, BridgeHandle>> = Mutex::new(HashMap::new());
+}
+
+impl Default for BridgeHandle {
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(EventBridge::new())),
+        }
     }
 }
 
-#[wasm_bindgen]
-pub fn js_set_permission_status(granted: bool, requested: bool, available: bool) {
-    let status = PermissionStatus {
-        granted,
-        requested,
-        available,
-    };
+impl BridgeHandle {
+    /// Create an unregistered handle, e.g. to inject into a test `App`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `f` with the bridge locked; `None` if the lock is poisoned
+    pub fn with<R>(&self, f: impl FnOnce(&mut EventBridge) -> R) -> Option<R> {
+        self.inner.lock().ok().map(|mut bridge| f(&mut bridge))
+    }
+
+    /// Make this handle the one page scripts reach through `canvas_id`, until the
+    /// returned registration is dropped
+    pub fn register(&self, canvas_id: &str) -> BridgeRegistration {
+        if let Ok(mut bridges) = BRIDGES.lock() {
+            bridges.insert(canvas_id.to_string(), self.clone());
+        }
+        BridgeRegistration {
+            canvas_id: canvas_id.to_string(),
+            handle: self.clone(),
+        }
+    }
+
+    /// Whether both handles share the same queues
+    fn same_bridge(&self, other: &BridgeHandle) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
 
-    if let Ok(mut bridge) = BRIDGE.lock() {
-        bridge.set_permission_status(status);
+    fn push_result(&self, (events, report): (Vec<BridgeEvent>, BatchReport)) -> String {
+        self.with(|bridge| {
+            bridge.push_events(events);
+            report.to_json()
+        })
+        .unwrap_or_else(bridge_unavailable)
     }
 }
 
+/// Keeps a handle registered under its canvas id for as long as the owning `App`
+/// lives. Dropping it removes the entry, so a remounted canvas starts with fresh queues.
+#[derive(Resource)]
+pub struct BridgeRegistration {
+    canvas_id: String,
+    handle: BridgeHandle,
+}
+
+impl Drop for BridgeRegistration {
+    fn drop(&mut self) {
+        let mut bridges = BRIDGES.lock().unwrap_or_else(|err| err.into_inner());
+        // A newer game may already have taken over the canvas id
+        if bridges
+            .get(&self.canvas_id)
+            .is_some_and(|handle| handle.same_bridge(&self.handle))
+        {
+            bridges.remove(&self.canvas_id);
+        }
+    }
+}
+
+fn bridge_unavailable() -> String {
+    serde_json::json!({ "ok": false, "error": "bridge unavailable" }).to_string()
+}
+
+// WASM-bindgen interface for JavaScript interaction.
+// `BridgeHandle.for_canvas(id)` addresses one game; the `js_*` functions address the
+// game on the default canvas.
 #[wasm_bindgen]
-pub fn js_request_game_state(state: &str) -> String {
-    let event = BridgeEvent::GameStateRequest {
-        state: state.to_string(),
-    };
+impl BridgeHandle {
+    /// Get the handle for the game rendering to `canvas_id`, creating it if the game
+    /// hasn't started yet so early events aren't lost
+    pub fn for_canvas(canvas_id: &str) -> BridgeHandle {
+        let mut bridges = BRIDGES.lock().unwrap_or_else(|err| err.into_inner());
+        bridges.entry(canvas_id.to_string()).or_default().clone()
+    }
+
+    pub fn push_device_orientation(&self, alpha: f32, beta: f32, gamma: f32, timestamp: f64) {
+        let data = DeviceOrientationData {
+            alpha,
+            beta,
+            gamma,
+            timestamp,
+        };
+        self.with(|bridge| bridge.push_event(BridgeEvent::DeviceOrientation(data)));
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn push_device_motion(
+        &self,
+        accel_x: f32,
+        accel_y: f32,
+        accel_z: f32,
+        rate_alpha: f32,
+        rate_beta: f32,
+        rate_gamma: f32,
+        interval: f64,
+        timestamp: f64,
+    ) {
+        let data = DeviceMotionData {
+            acceleration_including_gravity: AccelerationData {
+                x: accel_x,
+                y: accel_y,
+                z: accel_z,
+            },
+            rotation_rate: RotationRateData {
+                alpha: rate_alpha,
+                beta: rate_beta,
+                gamma: rate_gamma,
+            },
+            interval,
+            timestamp,
+        };
+        self.with(|bridge| bridge.push_event(BridgeEvent::DeviceMotion(data)));
+    }
+
+    pub fn push_screen_orientation(&self, angle: i32, timestamp: f64) {
+        let data = ScreenOrientationData { angle, timestamp };
+        self.with(|bridge| bridge.push_event(BridgeEvent::ScreenOrientation(data)));
+    }
+
+    /// Push many events in one call: `{"version": 1, "events": [{"type": "DeviceOrientation", ...}]}`.
+    /// Returns a JSON `BatchReport` listing malformed entries by index.
+    pub fn push_event_batch(&self, json: &str) -> String {
+        self.push_result(super::parse_batch(json))
+    }
+
+    /// Push orientation samples from a `Float64Array` of alpha, beta, gamma, timestamp
+    pub fn push_orientation_samples(&self, version: u32, samples: &[f64]) -> String {
+        self.push_result(super::parse_orientation_samples(version, samples))
+    }
+
+    /// Push motion samples from a `Float64Array` of accel x, y, z, rate alpha, beta, gamma,
+    /// interval, timestamp
+    pub fn push_motion_samples(&self, version: u32, samples: &[f64]) -> String {
+        self.push_result(super::parse_motion_samples(version, samples))
+    }
+
+    /// Configure the inbound queue: `coalesce` is `none`, `latest` or `average`
+    pub fn set_queue_policy(&self, capacity: u32, coalesce: &str) -> String {
+        let coalesce: CoalesceMode = match serde_json::from_value(serde_json::json!(coalesce)) {
+            Ok(mode) => mode,
+            Err(err) => {
+                return serde_json::json!({ "ok": false, "error": err.to_string() }).to_string()
+            }
+        };
+        if capacity == 0 {
+            return serde_json::json!({ "ok": false, "error": "capacity must be at least 1" })
+                .to_string();
+        }
+
+        self.with(|bridge| {
+            bridge.set_queue_policy(QueuePolicy {
+                capacity: capacity as usize,
+                coalesce,
+            });
+            serde_json::json!({ "ok": true }).to_string()
+        })
+        .unwrap_or_else(bridge_unavailable)
+    }
+
+    pub fn set_permission_status(&self, granted: bool, requested: bool, available: bool) {
+        let status = PermissionStatus {
+            granted,
+            requested,
+            available,
+        };
+        self.with(|bridge| bridge.set_permission_status(status));
+    }
+
+    /// Send a remote control command as JSON, e.g. `{"id": 1, "command": "start", "mode": "classic"}`.
+    /// Returns whether it was queued; the outcome arrives later as an outbound `CommandResult`.
+    pub fn send_command(&self, json: &str) -> String {
+        let request: HostCommandRequest = match serde_json::from_str(json) {
+            Ok(request) => request,
+            Err(err) => {
+                return serde_json::json!({ "ok": false, "error": err.to_string() }).to_string();
+            }
+        };
+
+        let id = request.id;
+        self.with(|bridge| {
+            bridge.push_event(BridgeEvent::HostCommand(request));
+            serde_json::json!({ "ok": true, "id": id }).to_string()
+        })
+        .unwrap_or_else(bridge_unavailable)
+    }
+
+    /// Describe the host environment (a JSON `HostCapabilities`). The game answers with
+    /// an outbound `HandshakeAck` carrying its capabilities and chosen input defaults.
+    pub fn handshake(&self, json: &str) -> String {
+        let host: HostCapabilities = match serde_json::from_str(json) {
+            Ok(host) => host,
+            Err(err) => {
+                return serde_json::json!({ "ok": false, "error": err.to_string() }).to_string();
+            }
+        };
 
-    if let Ok(mut bridge) = BRIDGE.lock() {
-        bridge.push_event(event);
+        self.with(|bridge| {
+            bridge.push_event(BridgeEvent::Handshake(host));
+            serde_json::json!({ "ok": true }).to_string()
+        })
+        .unwrap_or_else(bridge_unavailable)
     }
 
-    // Return empty string for now, proper response handling would be async
-    String::new()
+    /// Query a named view (`score`, `state`, `tower`, `input`, `settings`, `all`) as JSON.
+    /// Answered synchronously from the snapshot published at the end of the last frame.
+    pub fn request_game_state(&self, state: &str) -> String {
+        self.with(|bridge| bridge.query_view(state))
+            .unwrap_or_else(|| serde_json::json!({ "error": "bridge unavailable" }).to_string())
+    }
+
+    /// Drain game-to-host events as a JSON `OutboundBatch`; call once per animation frame
+    pub fn drain_outbound_events(&self) -> String {
+        self.with(|bridge| serde_json::to_string(&bridge.drain_outbound()).unwrap_or_default())
+            .unwrap_or_default()
+    }
+}
+
+fn default_bridge() -> BridgeHandle {
+    BridgeHandle::for_canvas(DEFAULT_CANVAS_ID)
+}
+
+#[wasm_bindgen]
+pub fn js_push_device_orientation(alpha: f32, beta: f32, gamma: f32, timestamp: f64) {
+    default_bridge().push_device_orientation(alpha, beta, gamma, timestamp);
+}
+
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn js_push_device_motion(
+    accel_x: f32,
+    accel_y: f32,
+    accel_z: f32,
+    rate_alpha: f32,
+    rate_beta: f32,
+    rate_gamma: f32,
+    interval: f64,
+    timestamp: f64,
+) {
+    default_bridge().push_device_motion(
+        accel_x, accel_y, accel_z, rate_alpha, rate_beta, rate_gamma, interval, timestamp,
+    );
+}
+
+#[wasm_bindgen]
+pub fn js_push_screen_orientation(angle: i32, timestamp: f64) {
+    default_bridge().push_screen_orientation(angle, timestamp);
+}
+
+#[wasm_bindgen]
+pub fn js_push_event_batch(json: &str) -> String {
+    default_bridge().push_event_batch(json)
+}
+
+#[wasm_bindgen]
+pub fn js_push_orientation_samples(version: u32, samples: &[f64]) -> String {
+    default_bridge().push_orientation_samples(version, samples)
+}
+
+#[wasm_bindgen]
+pub fn js_push_motion_samples(version: u32, samples: &[f64]) -> String {
+    default_bridge().push_motion_samples(version, samples)
+}
+
+#[wasm_bindgen]
+pub fn js_set_queue_policy(capacity: u32, coalesce: &str) -> String {
+    default_bridge().set_queue_policy(capacity, coalesce)
+}
+
+/// Highest inbound protocol version this build understands
+#[wasm_bindgen]
+pub fn js_protocol_version() -> u32 {
+    super::PROTOCOL_VERSION
+}
+
+#[wasm_bindgen]
+pub fn js_set_permission_status(granted: bool, requested: bool, available: bool) {
+    default_bridge().set_permission_status(granted, requested, available);
+}
+
+#[wasm_bindgen]
+pub fn js_send_command(json: &str) -> String {
+    default_bridge().send_command(json)
+}
+
+#[wasm_bindgen]
+pub fn js_handshake(json: &str) -> String {
+    default_bridge().handshake(json)
+}
+
+#[wasm_bindgen]
+pub fn js_request_game_state(state: &str) -> String {
+    default_bridge().request_game_state(state)
+}
+
+#[wasm_bindgen]
+pub fn js_drain_outbound_events() -> String {
+    default_bridge().drain_outbound_events()
 }
 
 // Bevy resource for managing the bridge
-#[derive(Resource, Default)]
+#[derive(Resource)]
 pub struct JsRustBridge {
+    handle: BridgeHandle,
     pub last_orientation: Option<DeviceOrientationData>,
     pub permission_status: PermissionStatus,
     pub events_processed: u32,
+    pub events_dropped: u64,
+    pub events_coalesced: u64,
+    pub orientation_stats: StreamStats,
+    pub motion_stats: StreamStats,
+}
+
+impl FromWorld for JsRustBridge {
+    /// Uses an injected `BridgeHandle` resource if present. Otherwise wasm builds use the
+    /// handle registered for the primary window's canvas and native builds get a fresh one.
+    fn from_world(world: &mut World) -> Self {
+        let handle = match world.get_resource::<BridgeHandle>() {
+            Some(handle) => handle.clone(),
+            None => {
+                let handle = Self::default_handle(world);
+                world.insert_resource(handle.clone());
+                handle
+            }
+        };
+
+        Self::new(handle)
+    }
 }
 
 impl JsRustBridge {
+    pub fn new(handle: BridgeHandle) -> Self {
+        Self {
+            handle,
+            last_orientation: None,
+            permission_status: PermissionStatus::default(),
+            events_processed: 0,
+            events_dropped: 0,
+            events_coalesced: 0,
+            orientation_stats: StreamStats::default(),
+            motion_stats: StreamStats::default(),
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn default_handle(world: &mut World) -> BridgeHandle {
+        use bevy::window::PrimaryWindow;
+
+        let canvas_id = world
+            .query_filtered::<&Window, With<PrimaryWindow>>()
+            .get_single(world)
+            .ok()
+            .and_then(|window| window.canvas.clone())
+            .map(|selector| selector.trim_start_matches('#').to_string())
+            .unwrap_or_else(|| DEFAULT_CANVAS_ID.to_string());
+
+        // Picks up a handle the page created before the game started, if any
+        let handle = BridgeHandle::for_canvas(&canvas_id);
+        let registration = handle.register(&canvas_id);
+        world.insert_resource(registration);
+        handle
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn default_handle(_world: &mut World) -> BridgeHandle {
+        BridgeHandle::new()
+    }
+
+    /// Handle to this game's queues
+    pub fn handle(&self) -> &BridgeHandle {
+        &self.handle
+    }
+
     pub fn process_events(&mut self) -> Vec<BridgeEvent> {
         let mut events = Vec::new();
 
-        if let Ok(mut bridge) = BRIDGE.lock() {
+        let handle = self.handle.clone();
+        if let Ok(mut bridge) = handle.inner.lock() {
             while let Some(event) = bridge.pop_event() {
                 match &event {
                     BridgeEvent::DeviceOrientation(data) => {
@@ -141,33 +797,93 @@ impl JsRustBridge {
                 events.push(event);
                 self.events_processed += 1;
             }
+            self.events_dropped = bridge.dropped_events();
+            self.events_coalesced = bridge.coalesced_events();
+            let (orientation, motion) = bridge.stream_stats();
+            self.orientation_stats.clone_from(orientation);
+            self.motion_stats.clone_from(motion);
         }
 
         events
     }
 
+    /// Change how the inbound queue handles bursts of sensor samples
+    pub fn set_queue_policy(&self, policy: QueuePolicy) {
+        self.handle.with(|bridge| bridge.set_queue_policy(policy));
+    }
+
+    /// Send an event to the host page
+    pub fn send_outbound(&self, event: OutboundEvent) {
+        self.handle.with(|bridge| bridge.push_outbound(event));
+    }
+
     pub fn send_game_state_response(&self, state: &str, data: &str) {
-        let event = BridgeEvent::GameStateResponse {
+        self.send_outbound(OutboundEvent::GameStateResponse {
             state: state.to_string(),
             data: data.to_string(),
-        };
+        });
+    }
 
-        if let Ok(mut bridge) = BRIDGE.lock() {
-            bridge.push_event(event);
-        }
+    /// Acknowledge a host command, with the reason when it was rejected
+    pub fn send_command_result(&self, id: u64, result: Result<(), String>) {
+        self.send_outbound(OutboundEvent::CommandResult {
+            id,
+            ok: result.is_ok(),
+            error: result.err(),
+        });
+    }
+
+    /// Publish the snapshot served to host page queries
+    pub fn publish_snapshot(&self, snapshot: GameSnapshot) {
+        self.handle.with(|bridge| bridge.set_snapshot(snapshot));
+    }
+
+    /// Ask the host page to vibrate
+    pub fn send_haptic(&self, duration_ms: u32) {
+        self.send_outbound(OutboundEvent::Haptic { duration_ms });
+    }
+
+    /// Ask the host page to play a sound
+    pub fn send_audio_cue(&self, cue: &str) {
+        self.send_outbound(OutboundEvent::AudioCue {
+            cue: cue.to_string(),
+        });
     }
 }
 
 // Bevy system for processing bridge events
+#[allow(clippy::too_many_arguments)]
 pub fn process_bridge_events(
     mut bridge: ResMut<JsRustBridge>,
     mut tilt_input: ResMut<crate::input::TiltInput>,
+    mut arbiter: ResMut<crate::input::InputArbiter>,
+    mut diagnostics: ResMut<crate::input::SensorDiagnostics>,
+    mut host_commands: EventWriter<HostCommandRequest>,
+    mut environment: ResMut<crate::input::HostEnvironment>,
+    mut focus_changes: EventWriter<crate::input::AppFocusChanged>,
+    time: Res<Time>,
 ) {
     let events = bridge.process_events();
+    diagnostics.record_frame(events.len() as u32);
+    diagnostics.queue_overflows = bridge.events_dropped;
+    diagnostics.coalesced_samples = bridge.events_coalesced;
+    // Stream stats come from the bridge so coalescing and frame timing don't skew them
+    diagnostics
+        .orientation
+        .clone_from(&bridge.orientation_stats);
+    diagnostics.motion.clone_from(&bridge.motion_stats);
+    let now = time.elapsed_seconds_f64() * 1000.0;
+    let device_active = tilt_input.input_source == crate::input::InputSource::Device;
 
     for event in events {
         match event {
             BridgeEvent::DeviceOrientation(data) => {
+                // Sensor data keeps the stream fresh even while another source is active
+                arbiter.record_activity(crate::input::InputSource::Device, now);
+                if !device_active {
+                    continue;
+                }
+
                 // Update tilt input with full orientation data
                 tilt_input.update_orientation(data.alpha, data.beta, data.gamma, data.timestamp);
                 tilt_input.enabled = bridge.permission_status.granted;
@@ -177,6 +893,25 @@ pub fn process_bridge_events(
                     info!("Device orientation: {}", tilt_input.get_debug_info());
                 }
             }
+            BridgeEvent::DeviceMotion(data) => {
+                arbiter.record_activity(crate::input::InputSource::Device, now);
+                if !device_active {
+                    continue;
+                }
+
+                let accel = &data.acceleration_including_gravity;
+                let rate = &data.rotation_rate;
+                tilt_input.update_motion(
+                    Vec3::new(accel.x, accel.y, accel.z),
+                    Vec3::new(rate.alpha, rate.beta, rate.gamma),
+                    data.timestamp,
+                );
+                tilt_input.enabled = bridge.permission_status.granted;
+            }
+            BridgeEvent::ScreenOrientation(data) => {
+                tilt_input
+                    .set_screen_rotation(crate::input::ScreenRotation::from_angle(data.angle));
+            }
             BridgeEvent::PermissionStatusChanged(status) => {
                 info!(
                     "Permission status changed: granted={}, available={}",
@@ -184,13 +919,248 @@ pub fn process_bridge_events(
                 );
                 tilt_input.enabled = status.granted;
             }
-            BridgeEvent::GameStateRequest { state } => {
-                info!("Game state requested: {}", state);
-                // Handle game state requests here
+            BridgeEvent::HostCommand(request) => {
+                host_commands.send(request);
+            }
+            BridgeEvent::Handshake(host) => {
+                let reply = environment.apply(host, &mut tilt_input, &mut arbiter);
+                bridge.send_outbound(OutboundEvent::HandshakeAck(reply));
+            }
+            BridgeEvent::VisibilityChanged { visible } => {
+                focus_changes.send(crate::input::AppFocusChanged { focused: visible });
+            }
+            BridgeEvent::PageHide { persisted } => {
+                debug!("Page hidden (persisted: {})", persisted);
+                focus_changes.send(crate::input::AppFocusChanged { focused: false });
             }
-            BridgeEvent::GameStateResponse { state: _, data: _ } => {
-                // This would typically be handled by JS side
+            BridgeEvent::FocusChanged { focused } => {
+                focus_changes.send(crate::input::AppFocusChanged { focused });
             }
         }
     }
 }
+
+// Bevy system for forwarding score and state changes to the host page
+pub fn publish_outbound_events(
+    bridge: Res<JsRustBridge>,
+    score: Option<Res<crate::GameScore>>,
+    state: Res<State<crate::GameState>>,
+) {
+    if state.is_changed() {
+        bridge.send_outbound(OutboundEvent::StateChanged {
+            state: format!("{:?}", state.get()),
+        });
+    }
+
+    if let Some(score) = score {
+        if score.is_changed() {
+            bridge.send_outbound(OutboundEvent::ScoreChanged {
+                current: score.current,
+                best: score.best,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn orientation(gamma: f32, timestamp: f64) -> BridgeEvent {
+        BridgeEvent::DeviceOrientation(DeviceOrientationData {
+            alpha: 0.0,
+            beta: 0.0,
+            gamma,
+            timestamp,
+        })
+    }
+
+    fn motion(timestamp: f64) -> BridgeEvent {
+        BridgeEvent::DeviceMotion(DeviceMotionData {
+            acceleration_including_gravity: AccelerationData {
+                x: 0.0,
+                y: 0.0,
+                z: 9.81,
+            },
+            rotation_rate: RotationRateData {
+                alpha: 0.0,
+                beta: 0.0,
+                gamma: 0.0,
+            },
+            interval: 16.0,
+            timestamp,
+        })
+    }
+
+    fn drain(bridge: &mut EventBridge) -> Vec<BridgeEvent> {
+        std::iter::from_fn(|| bridge.pop_event()).collect()
+    }
+
+    fn gammas(events: &[BridgeEvent]) -> Vec<Option<f32>> {
+        events
+            .iter()
+            .map(|event| match event {
+                BridgeEvent::DeviceOrientation(data) => Some(data.gamma),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn latest_coalescing_keeps_the_newest_tail_sample() {
+        let mut bridge = EventBridge::new();
+        bridge.push_event(orientation(1.0, 0.0));
+        bridge.push_event(orientation(2.0, 16.0));
+        bridge.push_event(orientation(3.0, 32.0));
+
+        assert_eq!(gammas(&drain(&mut bridge)), [Some(3.0)]);
+        assert_eq!(bridge.coalesced_events(), 2);
+        // Stream stats still see every sample that arrived
+        assert_eq!(bridge.stream_stats().0.samples, 3);
+    }
+
+    #[test]
+    fn average_coalescing_merges_into_the_running_mean() {
+        let mut bridge = EventBridge::new();
+        bridge.set_queue_policy(QueuePolicy {
+            coalesce: CoalesceMode::Average,
+            ..QueuePolicy::default()
+        });
+        bridge.push_event(orientation(10.0, 0.0));
+        bridge.push_event(orientation(20.0, 16.0));
+        bridge.push_event(orientation(30.0, 32.0));
+
+        assert_eq!(gammas(&drain(&mut bridge)), [Some(20.0)]);
+    }
+
+    #[test]
+    fn orientation_is_never_merged_across_other_events() {
+        let mut bridge = EventBridge::new();
+        bridge.push_event(orientation(1.0, 0.0));
+        bridge.push_event(motion(8.0));
+        bridge.push_event(orientation(2.0, 16.0));
+        bridge.push_event(BridgeEvent::FocusChanged { focused: false });
+        bridge.push_event(orientation(3.0, 32.0));
+
+        let events = drain(&mut bridge);
+        assert_eq!(
+            gammas(&events),
+            [Some(1.0), None, Some(2.0), None, Some(3.0)]
+        );
+        assert!(matches!(events[1], BridgeEvent::DeviceMotion(_)));
+        assert_eq!(bridge.coalesced_events(), 0);
+    }
+
+    #[test]
+    fn full_queue_evicts_the_oldest_sensor_sample_only() {
+        let mut bridge = EventBridge::new();
+        bridge.set_queue_policy(QueuePolicy {
+            capacity: 2,
+            coalesce: CoalesceMode::None,
+        });
+        bridge.push_event(BridgeEvent::FocusChanged { focused: true });
+        for index in 0..4 {
+            bridge.push_event(orientation(index as f32, index as f64 * 16.0));
+        }
+
+        let events = drain(&mut bridge);
+        assert!(matches!(
+            events[0],
+            BridgeEvent::FocusChanged { focused: true }
+        ));
+        assert_eq!(gammas(&events[1..]), [Some(2.0), Some(3.0)]);
+        assert_eq!(bridge.dropped_events(), 2);
+        assert_eq!(bridge.stream_stats().0.samples, 4);
+    }
+
+    fn bridge_app(handle: BridgeHandle) -> App {
+        let mut app = App::new();
+        app.insert_resource(handle)
+            .init_resource::<Time>()
+            .init_resource::<JsRustBridge>()
+            .init_resource::<crate::input::TiltInput>()
+            .init_resource::<crate::input::InputArbiter>()
+            .init_resource::<crate::input::SensorDiagnostics>()
+            .init_resource::<crate::input::HostEnvironment>()
+            .add_event::<HostCommandRequest>()
+            .add_event::<crate::input::AppFocusChanged>()
+            .add_systems(Update, process_bridge_events);
+        app
+    }
+
+    #[test]
+    fn apps_with_separate_handles_stay_isolated() {
+        let (first_handle, second_handle) = (BridgeHandle::new(), BridgeHandle::new());
+        let mut first = bridge_app(first_handle.clone());
+        let mut second = bridge_app(second_handle.clone());
+
+        first_handle.with(|bridge| {
+            bridge.push_event(orientation(20.0, 0.0));
+            bridge.push_event(BridgeEvent::HostCommand(HostCommandRequest {
+                id: 7,
+                command: HostCommand::Pause,
+            }));
+        });
+        first.update();
+        second.update();
+
+        let tilt = |app: &App| app.world().resource::<crate::input::TiltInput>().gamma;
+        let samples = |app: &App| {
+            app.world()
+                .resource::<crate::input::SensorDiagnostics>()
+                .orientation
+                .samples
+        };
+        let commands = |app: &App| app.world().resource::<Events<HostCommandRequest>>().len();
+
+        assert_eq!(tilt(&first), 20.0);
+        assert_eq!((samples(&first), commands(&first)), (1, 1));
+        assert_eq!(tilt(&second), 0.0);
+        assert_eq!((samples(&second), commands(&second)), (0, 0));
+
+        // And the other way round
+        second_handle.with(|bridge| bridge.push_event(orientation(-5.0, 0.0)));
+        second.update();
+        assert_eq!(tilt(&second), -5.0);
+        assert_eq!(tilt(&first), 20.0);
+    }
+
+    fn registered(canvas_id: &str) -> Option<BridgeHandle> {
+        BRIDGES.lock().unwrap().get(canvas_id).cloned()
+    }
+
+    #[test]
+    fn dropping_an_app_unregisters_its_canvas() {
+        let handle = BridgeHandle::new();
+        let mut app = bridge_app(handle.clone());
+        app.insert_resource(handle.register("drop-test"));
+        app.update();
+        assert!(registered("drop-test").is_some_and(|found| found.same_bridge(&handle)));
+
+        drop(app);
+        assert!(registered("drop-test").is_none());
+
+        // A remounted canvas gets fresh queues rather than the stale ones
+        let remounted = BridgeHandle::for_canvas("drop-test");
+        assert!(!remounted.same_bridge(&handle));
+        BRIDGES.lock().unwrap().remove("drop-test");
+    }
+
+    #[test]
+    fn dropping_a_replaced_registration_keeps_the_newer_handle() {
+        let (old_handle, new_handle) = (BridgeHandle::new(), BridgeHandle::new());
+        let old_app = {
+            let mut app = bridge_app(old_handle.clone());
+            app.insert_resource(old_handle.register("remount-test"));
+            app
+        };
+        let mut new_app = bridge_app(new_handle.clone());
+        new_app.insert_resource(new_handle.register("remount-test"));
+
+        drop(old_app);
+        assert!(registered("remount-test").is_some_and(|found| found.same_bridge(&new_handle)));
+
+        drop(new_app);
+        assert!(registered("remount-test").is_none());
+    }
+}
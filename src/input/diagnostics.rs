@@ -0,0 +1,107 @@
+use bevy::prelude::*;
+
+/// Smoothing factor for rate and jitter estimates
+const STATS_ALPHA: f64 = 0.05;
+
+/// Gaps longer than this (ms) are treated as pauses, not as a slow sample rate
+const MAX_SAMPLE_GAP: f64 = 1000.0;
+
+/// Arrival statistics for one sensor stream
+#[derive(Debug, Clone, Default)]
+pub struct StreamStats {
+    pub samples: u64,
+    pub sample_rate: f64,   // Effective rate in Hz
+    pub mean_interval: f64, // Milliseconds between samples
+    pub jitter: f64,        // Mean deviation from the mean interval, milliseconds
+    pub timestamp_regressions: u32,
+    pub gaps: u32, // Intervals longer than MAX_SAMPLE_GAP
+    last_timestamp: Option<f64>,
+}
+
+impl StreamStats {
+    /// Record a sample by its sensor timestamp (milliseconds)
+    pub fn record(&mut self, timestamp: f64) {
+        self.samples += 1;
+
+        let Some(last) = self.last_timestamp else {
+            self.last_timestamp = Some(timestamp);
+            return;
+        };
+
+        let interval = timestamp - last;
+        if interval < 0.0 {
+            // Keep the newer baseline so one bad timestamp counts once
+            self.timestamp_regressions += 1;
+            self.last_timestamp = Some(timestamp);
+            return;
+        }
+        self.last_timestamp = Some(timestamp);
+
+        if interval > MAX_SAMPLE_GAP {
+            self.gaps += 1;
+            return;
+        }
+
+        if self.mean_interval == 0.0 {
+            self.mean_interval = interval;
+        } else {
+            let deviation = (interval - self.mean_interval).abs();
+            self.jitter += STATS_ALPHA * (deviation - self.jitter);
+            self.mean_interval += STATS_ALPHA * (interval - self.mean_interval);
+        }
+
+        if self.mean_interval > 0.0 {
+            self.sample_rate = 1000.0 / self.mean_interval;
+        }
+    }
+}
+
+/// Sensor health metrics, for telling device issues apart from game issues
+#[derive(Resource, Debug, Clone, Default)]
+pub struct SensorDiagnostics {
+    pub orientation: StreamStats,
+    pub motion: StreamStats,
+    pub queue_overflows: u64, // Sensor samples dropped because the queue was full
+    pub coalesced_samples: u64, // Orientation samples merged into a queued one
+    pub events_last_frame: u32,
+    pub max_events_per_frame: u32,
+    pub empty_frames: u64, // Frames where no bridge event arrived
+}
+
+impl SensorDiagnostics {
+    /// Record how many bridge events were drained this frame
+    pub fn record_frame(&mut self, event_count: u32) {
+        self.events_last_frame = event_count;
+        self.max_events_per_frame = self.max_events_per_frame.max(event_count);
+        if event_count == 0 {
+            self.empty_frames += 1;
+        }
+    }
+
+    /// Get a multi-line summary for the debug panel
+    pub fn get_report(&self) -> String {
+        let stream = |name: &str, stats: &StreamStats| {
+            format!(
+                "{}: {:.1} Hz | interval {:.1} ms | jitter {:.2} ms | regressions {} | gaps {} | samples {}",
+                name,
+                stats.sample_rate,
+                stats.mean_interval,
+                stats.jitter,
+                stats.timestamp_regressions,
+                stats.gaps,
+                stats.samples
+            )
+        };
+
+        format!(
+            "{}\n{}\nQueue: overflows {} | coalesced {} | events/frame {} (max {}) | empty frames {}",
+            stream("Orientation", &self.orientation),
+            stream("Motion", &self.motion),
+            self.queue_overflows,
+            self.coalesced_samples,
+            self.events_last_frame,
+            self.max_events_per_frame,
+            self.empty_frames
+        )
+    }
+}
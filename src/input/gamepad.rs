@@ -0,0 +1,113 @@
+use super::{InputSource, TiltInput};
+use bevy::input::gamepad::GamepadConnectionEvent;
+use bevy::prelude::*;
+
+/// Analog stick tuning for gamepad tilt control
+#[derive(Resource)]
+pub struct GamepadTiltSettings {
+    pub dead_zone: f32,         // Radial dead zone in stick units (0.0 to 1.0)
+    pub response_exponent: f32, // 1.0 is linear, higher gives finer control near centre
+    pub max_tilt: f32,          // Simulated tilt in degrees at full deflection
+}
+
+impl Default for GamepadTiltSettings {
+    fn default() -> Self {
+        Self {
+            dead_zone: 0.15,
+            response_exponent: 1.6,
+            max_tilt: 45.0,
+        }
+    }
+}
+
+impl GamepadTiltSettings {
+    /// Apply radial dead zone and response curve to a raw stick vector
+    pub fn shape_stick(&self, stick: Vec2) -> Vec2 {
+        let magnitude = stick.length().min(1.0);
+        if magnitude <= self.dead_zone {
+            return Vec2::ZERO;
+        }
+
+        // Rescale so output starts at zero at the edge of the dead zone
+        let rescaled = (magnitude - self.dead_zone) / (1.0 - self.dead_zone);
+        let curved = rescaled.powf(self.response_exponent);
+
+        stick / stick.length() * curved
+    }
+}
+
+/// Gamepad currently driving tilt, plus the source to restore when it disconnects
+#[derive(Resource, Default)]
+pub struct ActiveGamepad {
+    pub gamepad: Option<Gamepad>,
+    pub previous_source: Option<InputSource>,
+}
+
+/// System for offering gamepads automatically as they connect and disconnect
+pub fn detect_gamepad_connections(
+    mut connection_events: EventReader<GamepadConnectionEvent>,
+    gamepads: Res<Gamepads>,
+    mut active_gamepad: ResMut<ActiveGamepad>,
+    mut tilt_input: ResMut<TiltInput>,
+) {
+    for event in connection_events.read() {
+        if event.connected() {
+            if active_gamepad.gamepad.is_none() {
+                info!(
+                    "Gamepad {} connected, switching tilt control",
+                    event.gamepad.id
+                );
+                active_gamepad.gamepad = Some(event.gamepad);
+                if tilt_input.input_source != InputSource::Gamepad {
+                    active_gamepad.previous_source = Some(tilt_input.input_source);
+                    tilt_input.set_input_source(InputSource::Gamepad);
+                }
+            }
+        } else if active_gamepad.gamepad == Some(event.gamepad) {
+            info!("Gamepad {} disconnected", event.gamepad.id);
+
+            // Hand over to another connected pad, or fall back to the previous source
+            active_gamepad.gamepad = gamepads.iter().find(|gamepad| *gamepad != event.gamepad);
+            if active_gamepad.gamepad.is_none() && tilt_input.input_source == InputSource::Gamepad {
+                let source = active_gamepad
+                    .previous_source
+                    .take()
+                    .unwrap_or(InputSource::Keyboard);
+                tilt_input.set_input_source(source);
+            }
+        }
+    }
+}
+
+/// System for handling gamepad analog stick as tilt control.
+/// Buttons are resolved through the action bindings.
+pub fn handle_gamepad_tilt_input(
+    settings: Res<GamepadTiltSettings>,
+    active_gamepad: Res<ActiveGamepad>,
+    axes: Res<Axis<GamepadAxis>>,
+    mut tilt_input: ResMut<TiltInput>,
+    time: Res<Time>,
+) {
+    if tilt_input.input_source != InputSource::Gamepad {
+        return;
+    }
+    let Some(gamepad) = active_gamepad.gamepad else {
+        return;
+    };
+
+    let stick_x = axes
+        .get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX))
+        .unwrap_or(0.0);
+    let stick_y = axes
+        .get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickY))
+        .unwrap_or(0.0);
+    let stick = settings.shape_stick(Vec2::new(stick_x, stick_y));
+
+    // Stick right tilts right (gamma), stick up tilts forward (negative beta)
+    let gamma = stick.x * settings.max_tilt;
+    let beta = -stick.y * settings.max_tilt;
+
+    let current_time = time.elapsed_seconds_f64() * 1000.0;
+    tilt_input.update_orientation(0.0, beta, gamma, current_time);
+    tilt_input.enabled = true;
+}
@@ -0,0 +1,211 @@
+use super::{InputArbiter, InputSource, TiltInput, PROTOCOL_VERSION};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Platform {
+    Ios,
+    Android,
+    #[default]
+    Desktop,
+}
+
+/// Input hardware the host page can reach
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SensorCapabilities {
+    pub orientation: bool,
+    pub motion: bool,
+    pub gamepad: bool, // A pad is connected, not just the Gamepad API being present
+    pub touch: bool,
+}
+
+/// What the host page reports about its environment at startup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostCapabilities {
+    pub protocol_version: u32,
+    #[serde(default)]
+    pub platform: Platform,
+    #[serde(default)]
+    pub sensors: SensorCapabilities,
+    #[serde(default)]
+    pub screen_width: f32, // CSS pixels
+    #[serde(default)]
+    pub screen_height: f32,
+    #[serde(default = "default_pixel_ratio")]
+    pub device_pixel_ratio: f32,
+    #[serde(default)]
+    pub permission_gesture_required: bool, // iOS needs a tap before sensor permission
+}
+
+fn default_pixel_ratio() -> f32 {
+    1.0
+}
+
+/// Which on-screen controls the game shows
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ControlLayout {
+    /// Keyboard, mouse and gamepad; no on-screen controls
+    #[default]
+    Desktop,
+    /// On-screen drop button, plus the joystick when tilt comes from touch
+    Touch,
+}
+
+/// The game's side of the handshake, sent back to the host page
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameCapabilities {
+    pub protocol_version: u32, // Version both sides will speak
+    pub input_sources: Vec<InputSource>,
+    pub commands: Vec<String>,
+    pub input_source: InputSource, // Chosen default
+    pub fallback_source: InputSource,
+    pub layout: ControlLayout,
+}
+
+/// Environment learned from the handshake; `host` is `None` until the page has answered
+#[derive(Resource, Debug, Clone, Default)]
+pub struct HostEnvironment {
+    pub host: Option<HostCapabilities>,
+    pub layout: ControlLayout,
+}
+
+impl HostEnvironment {
+    /// Pick the default and fallback input sources for a host
+    pub fn choose_sources(host: &HostCapabilities) -> (InputSource, InputSource) {
+        let sensors = &host.sensors;
+        let fallback = if sensors.touch {
+            InputSource::Virtual
+        } else {
+            InputSource::Keyboard
+        };
+
+        // Pads plugged in later are picked up by `detect_gamepad_connections`
+        let preferred =
+            if host.platform != Platform::Desktop && (sensors.orientation || sensors.motion) {
+                InputSource::Device
+            } else if sensors.gamepad {
+                InputSource::Gamepad
+            } else {
+                fallback
+            };
+
+        (preferred, fallback)
+    }
+
+    /// Pick the control layout for a host
+    pub fn choose_layout(host: &HostCapabilities) -> ControlLayout {
+        if host.sensors.touch {
+            ControlLayout::Touch
+        } else {
+            ControlLayout::Desktop
+        }
+    }
+
+    /// Adopt a host's capabilities, configuring input to match, and build the reply
+    pub fn apply(
+        &mut self,
+        host: HostCapabilities,
+        tilt_input: &mut TiltInput,
+        arbiter: &mut InputArbiter,
+    ) -> GameCapabilities {
+        let (preferred, fallback) = Self::choose_sources(&host);
+        self.layout = Self::choose_layout(&host);
+
+        info!(
+            "Handshake: {:?} host, sensors {:?}, {}x{} @{}x -> {:?} (fallback {:?}), {:?} layout",
+            host.platform,
+            host.sensors,
+            host.screen_width,
+            host.screen_height,
+            host.device_pixel_ratio,
+            preferred,
+            fallback,
+            self.layout
+        );
+
+        // The arbiter adopts the new source as its preference on its next run
+        arbiter.fallback = fallback;
+        if tilt_input.input_source != preferred {
+            tilt_input.reset_tilt();
+            tilt_input.set_input_source(preferred);
+        }
+
+        let reply = GameCapabilities {
+            protocol_version: host.protocol_version.min(PROTOCOL_VERSION),
+            input_sources: vec![
+                InputSource::Device,
+                InputSource::Keyboard,
+                InputSource::Virtual,
+                InputSource::Gamepad,
+                InputSource::Mouse,
+            ],
+            commands: [
+                "start",
+                "pause",
+                "resume",
+                "restart",
+                "quit_to_menu",
+                "set_input_source",
+                "calibrate",
+                "set_gravity_profile",
+            ]
+            .iter()
+            .map(|command| command.to_string())
+            .collect(),
+            input_source: preferred,
+            fallback_source: fallback,
+            layout: self.layout,
+        };
+
+        self.host = Some(host);
+        reply
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn desktop_host(gamepad: bool) -> HostCapabilities {
+        HostCapabilities {
+            protocol_version: PROTOCOL_VERSION,
+            platform: Platform::Desktop,
+            sensors: SensorCapabilities {
+                orientation: true, // Desktop browsers expose the API without a sensor
+                motion: true,
+                gamepad,
+                touch: false,
+            },
+            screen_width: 1280.0,
+            screen_height: 720.0,
+            device_pixel_ratio: 1.0,
+            permission_gesture_required: false,
+        }
+    }
+
+    #[test]
+    fn desktop_without_a_pad_uses_the_keyboard() {
+        let host = desktop_host(false);
+        assert_eq!(
+            HostEnvironment::choose_sources(&host),
+            (InputSource::Keyboard, InputSource::Keyboard)
+        );
+
+        let mut tilt_input = TiltInput::default();
+        let mut arbiter = InputArbiter::default();
+        let reply = HostEnvironment::default().apply(host, &mut tilt_input, &mut arbiter);
+        assert_eq!(reply.input_source, InputSource::Keyboard);
+        assert_eq!(tilt_input.input_source, InputSource::Keyboard);
+    }
+
+    #[test]
+    fn desktop_with_a_connected_pad_uses_it() {
+        assert_eq!(
+            HostEnvironment::choose_sources(&desktop_host(true)),
+            (InputSource::Gamepad, InputSource::Keyboard)
+        );
+    }
+}
@@ -1,19 +1,43 @@
-use super::TiltInput;
+use super::{Action, ActionState, InputSource, TiltInput};
 use bevy::prelude::*;
 
-pub fn handle_keyboard_input(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
+/// System for handling keyboard input as fallback tilt control
+pub fn handle_keyboard_tilt_input(
+    actions: Res<ActionState>,
     mut tilt_input: ResMut<TiltInput>,
+    time: Res<Time>,
 ) {
-    let beta;
+    if tilt_input.input_source != InputSource::Keyboard {
+        return;
+    }
+
+    let current_time = time.elapsed_seconds_f64() * 1000.0;
+    let mut beta = tilt_input.beta;
+    let mut gamma = tilt_input.gamma;
 
-    if keyboard_input.pressed(KeyCode::ArrowLeft) || keyboard_input.pressed(KeyCode::KeyA) {
-        beta = -10.0; // Simulate left tilt
-    } else if keyboard_input.pressed(KeyCode::ArrowRight) || keyboard_input.pressed(KeyCode::KeyD) {
-        beta = 10.0; // Simulate right tilt
-    } else {
-        beta = 0.0; // No tilt
+    // Simulate device tilt with keyboard
+    let tilt_speed = 30.0; // degrees per second
+    let delta_time = time.delta_seconds();
+    let tilt_increment = tilt_speed * delta_time;
+
+    if actions.pressed(Action::TiltLeft) {
+        gamma -= tilt_increment;
+    }
+    if actions.pressed(Action::TiltRight) {
+        gamma += tilt_increment;
     }
+    if actions.pressed(Action::TiltForward) {
+        beta -= tilt_increment;
+    }
+    if actions.pressed(Action::TiltBack) {
+        beta += tilt_increment;
+    }
+
+    // Clamp to reasonable ranges
+    beta = beta.clamp(-45.0, 45.0);
+    gamma = gamma.clamp(-45.0, 45.0);
 
-    tilt_input.beta = beta;
+    // Update tilt input with simulated data
+    tilt_input.update_orientation(0.0, beta, gamma, current_time);
+    tilt_input.enabled = true;
 }
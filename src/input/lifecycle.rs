@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+
+/// Sent when the game gains or loses the player's attention: tab visibility, page hide
+/// and window focus on the web, window focus on native
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AppFocusChanged {
+    pub focused: bool,
+}
+
+/// System forwarding native window focus changes
+#[cfg(not(target_arch = "wasm32"))]
+pub fn forward_window_focus(
+    mut window_focus: EventReader<bevy::window::WindowFocused>,
+    mut focus_changes: EventWriter<AppFocusChanged>,
+) {
+    for event in window_focus.read() {
+        focus_changes.send(AppFocusChanged {
+            focused: event.focused,
+        });
+    }
+}
@@ -1,9 +1,35 @@
+pub mod actions;
+pub mod arbitration;
 pub mod bridge;
 pub mod calibration;
+pub mod diagnostics;
+pub mod gamepad;
+pub mod handshake;
 pub mod keyboard;
+pub mod lifecycle;
+pub mod mouse;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod network;
+pub mod permission;
+pub mod protocol;
+pub mod snapshot;
 pub mod tilt;
+pub mod touch;
 
+pub use actions::*;
+pub use arbitration::*;
 pub use bridge::*;
 pub use calibration::*;
+pub use diagnostics::*;
+pub use gamepad::*;
+pub use handshake::*;
 pub use keyboard::*;
+pub use lifecycle::*;
+pub use mouse::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use network::*;
+pub use permission::*;
+pub use protocol::*;
+pub use snapshot::*;
 pub use tilt::*;
+pub use touch::*;
@@ -0,0 +1,80 @@
+use super::{InputSource, TiltInput};
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+/// How the mouse is turned into tilt
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MouseTiltMode {
+    /// Horizontal cursor offset from the screen centre
+    #[default]
+    CursorOffset,
+    /// Horizontal distance dragged while the left button is held
+    Drag,
+}
+
+/// Mouse tuning for tilt control
+#[derive(Resource)]
+pub struct MouseTiltSettings {
+    pub mode: MouseTiltMode,
+    pub max_tilt: f32,   // Simulated tilt in degrees at full offset
+    pub drag_range: f32, // Drag distance in logical pixels for full tilt
+}
+
+impl Default for MouseTiltSettings {
+    fn default() -> Self {
+        Self {
+            mode: MouseTiltMode::CursorOffset,
+            max_tilt: 45.0,
+            drag_range: 200.0,
+        }
+    }
+}
+
+/// System for handling mouse position or drag as tilt control
+pub fn handle_mouse_tilt_input(
+    settings: Res<MouseTiltSettings>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mut drag_anchor: Local<Option<Vec2>>,
+    mut tilt_input: ResMut<TiltInput>,
+    time: Res<Time>,
+) {
+    if tilt_input.input_source != InputSource::Mouse {
+        *drag_anchor = None;
+        return;
+    }
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+
+    let cursor = window.cursor_position();
+    let offset = match settings.mode {
+        MouseTiltMode::CursorOffset => {
+            // Hold the last tilt while the cursor is outside the window
+            let Some(cursor) = cursor else {
+                return;
+            };
+            (cursor.x - window.width() / 2.0) / (window.width() / 2.0)
+        }
+        MouseTiltMode::Drag => {
+            if mouse_buttons.just_pressed(MouseButton::Left) {
+                *drag_anchor = cursor;
+            }
+            if !mouse_buttons.pressed(MouseButton::Left) {
+                *drag_anchor = None;
+            }
+
+            match (*drag_anchor, cursor) {
+                (Some(anchor), Some(cursor)) => (cursor.x - anchor.x) / settings.drag_range,
+                _ => 0.0, // Released: level out
+            }
+        }
+    };
+
+    // Raw degrees go through the usual dead zone, sensitivity and smoothing
+    let gamma = offset.clamp(-1.0, 1.0) * settings.max_tilt;
+
+    let current_time = time.elapsed_seconds_f64() * 1000.0;
+    tilt_input.update_orientation(0.0, 0.0, gamma, current_time);
+    tilt_input.enabled = true;
+}
@@ -0,0 +1,288 @@
+use super::{ingest_batch, BridgeEvent, BridgeHandle, EventBatch, JsRustBridge, PermissionStatus};
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::io::ErrorKind;
+use std::net::{Ipv4Addr, SocketAddr, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// Environment variable enabling the feed: a port (`7878`) or address (`127.0.0.1:7878`)
+pub const SENSOR_FEED_ENV: &str = "TOWER_TUMBLER_SENSOR_ADDR";
+
+/// Largest datagram accepted; a batch of ~100 samples fits comfortably
+const MAX_PACKET_SIZE: usize = 16 * 1024;
+
+/// How often the receive thread wakes to check for shutdown
+const SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Where the native sensor feed listens. `None` leaves the feed off.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct SensorFeedConfig {
+    pub bind_addr: Option<SocketAddr>,
+}
+
+impl SensorFeedConfig {
+    /// Read the bind address from `TOWER_TUMBLER_SENSOR_ADDR`
+    pub fn from_env() -> Self {
+        let bind_addr = std::env::var(SENSOR_FEED_ENV).ok().and_then(|value| {
+            let addr = match value.parse::<u16>() {
+                Ok(port) => Ok(SocketAddr::from((Ipv4Addr::UNSPECIFIED, port))),
+                Err(_) => value.parse::<SocketAddr>(),
+            };
+            addr.map_err(|err| warn!("Ignoring {}={}: {}", SENSOR_FEED_ENV, value, err))
+                .ok()
+        });
+
+        Self { bind_addr }
+    }
+}
+
+/// Running feed. Dropping it, e.g. with the App, stops the receive thread.
+#[derive(Resource, Debug)]
+pub struct SensorFeed {
+    pub local_addr: SocketAddr, // Address actually bound, e.g. when configured with port 0
+    shutdown: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl SensorFeed {
+    /// Stop the receive thread and wait for it to exit
+    pub fn stop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            if thread.join().is_err() {
+                error!("Sensor feed thread panicked");
+            }
+        }
+    }
+}
+
+impl Drop for SensorFeed {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// One datagram's payload
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum SensorPacket {
+    Batch(EventBatch),
+    Single(BridgeEvent),
+}
+
+/// Parse one datagram: a single `BridgeEvent` or a versioned `EventBatch`.
+/// Only sensor and screen orientation events are accepted from the network.
+pub fn parse_sensor_packet(packet: &[u8]) -> Result<Vec<BridgeEvent>, String> {
+    let events = match serde_json::from_slice(packet).map_err(|err| err.to_string())? {
+        SensorPacket::Batch(batch) => {
+            let (events, report) = ingest_batch(batch);
+            if let Some(error) = report.error {
+                return Err(error);
+            }
+            if report.rejected > 0 {
+                warn!(
+                    "Sensor feed: {} malformed entries in batch",
+                    report.rejected
+                );
+            }
+            events
+        }
+        SensorPacket::Single(event) => vec![event],
+    };
+
+    Ok(events
+        .into_iter()
+        .filter(|event| {
+            event.is_sensor_sample() || matches!(event, BridgeEvent::ScreenOrientation(_))
+        })
+        .collect())
+}
+
+fn run_sensor_feed(socket: UdpSocket, handle: BridgeHandle, shutdown: Arc<AtomicBool>) {
+    let mut buffer = vec![0u8; MAX_PACKET_SIZE];
+    let mut last_sender = None;
+
+    while !shutdown.load(Ordering::SeqCst) {
+        let (len, sender) = match socket.recv_from(&mut buffer) {
+            Ok(received) => received,
+            // The read timeout expired; check for shutdown and listen again
+            Err(err) if matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                continue;
+            }
+            Err(err) => {
+                error!("Sensor feed stopped: {}", err);
+                return;
+            }
+        };
+
+        if last_sender != Some(sender) {
+            info!("Sensor feed receiving from {}", sender);
+            last_sender = Some(sender);
+        }
+
+        match parse_sensor_packet(&buffer[..len]) {
+            Ok(events) => {
+                handle.with(|bridge| bridge.push_events(events));
+            }
+            Err(err) => warn!("Sensor feed: bad packet from {}: {}", sender, err),
+        }
+    }
+}
+
+/// Startup system binding the UDP feed and streaming packets into this game's bridge
+pub fn start_sensor_feed(
+    mut commands: Commands,
+    config: Res<SensorFeedConfig>,
+    bridge: Res<JsRustBridge>,
+) {
+    let Some(bind_addr) = config.bind_addr else {
+        return;
+    };
+
+    let socket = match UdpSocket::bind(bind_addr) {
+        Ok(socket) => socket,
+        Err(err) => {
+            error!("Failed to bind sensor feed on {}: {}", bind_addr, err);
+            return;
+        }
+    };
+    let local_addr = socket.local_addr().unwrap_or(bind_addr);
+    if let Err(err) = socket.set_read_timeout(Some(SHUTDOWN_POLL_INTERVAL)) {
+        error!("Failed to configure sensor feed socket: {}", err);
+        return;
+    }
+
+    // A LAN sender needs no browser permission, so the device source is usable right away
+    let handle = bridge.handle().clone();
+    handle.with(|bridge| {
+        bridge.set_permission_status(PermissionStatus {
+            granted: true,
+            requested: false,
+            available: true,
+        })
+    });
+
+    let shutdown = Arc::new(AtomicBool::new(false));
+    let stop_signal = shutdown.clone();
+    let thread = match std::thread::Builder::new()
+        .name("sensor-feed".to_string())
+        .spawn(move || run_sensor_feed(socket, handle, stop_signal))
+    {
+        Ok(thread) => thread,
+        Err(err) => {
+            error!("Failed to start sensor feed thread: {}", err);
+            return;
+        }
+    };
+
+    info!("Sensor feed listening on udp://{}", local_addr);
+    commands.insert_resource(SensorFeed {
+        local_addr,
+        shutdown,
+        thread: Some(thread),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{CoalesceMode, QueuePolicy};
+    use std::time::Instant;
+
+    const SINGLE: &str =
+        r#"{"type": "DeviceOrientation", "alpha": 0, "beta": 0, "gamma": 12, "timestamp": 1}"#;
+    const BATCH: &str = r#"{"version": 1, "events": [
+        {"type": "DeviceOrientation", "alpha": 0, "beta": 0, "gamma": 1, "timestamp": 2},
+        {"type": "ScreenOrientation", "angle": 90, "timestamp": 3},
+        {"type": "FocusChanged", "focused": false}
+    ]}"#;
+
+    #[test]
+    fn single_event_packet() {
+        let events = parse_sensor_packet(SINGLE.as_bytes()).unwrap();
+        assert!(
+            matches!(&events[..], [BridgeEvent::DeviceOrientation(data)] if data.gamma == 12.0)
+        );
+    }
+
+    #[test]
+    fn batch_packet_keeps_only_sensor_events() {
+        let events = parse_sensor_packet(BATCH.as_bytes()).unwrap();
+        assert!(matches!(
+            &events[..],
+            [
+                BridgeEvent::DeviceOrientation(_),
+                BridgeEvent::ScreenOrientation(_)
+            ]
+        ));
+    }
+
+    #[test]
+    fn single_event_mentioning_events_is_not_a_batch() {
+        // Parsed by shape, so an "events" string elsewhere doesn't matter
+        let packet = r#"{"type": "PageHide", "persisted": false, "note": "\"events\""}"#;
+        assert_eq!(parse_sensor_packet(packet.as_bytes()).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn malformed_packets_are_errors() {
+        assert!(parse_sensor_packet(b"not json").is_err());
+        assert!(parse_sensor_packet(&[0xff, 0xfe]).is_err());
+        assert!(parse_sensor_packet(br#"{"version": 99, "events": []}"#).is_err());
+    }
+
+    #[test]
+    fn loopback_feed_delivers_packets_to_the_bridge() {
+        // Keep every sample so delivery can be checked one packet at a time
+        let handle = BridgeHandle::new();
+        handle.with(|bridge| {
+            bridge.set_queue_policy(QueuePolicy {
+                coalesce: CoalesceMode::None,
+                ..QueuePolicy::default()
+            })
+        });
+        let mut app = App::new();
+        app.insert_resource(SensorFeedConfig {
+            bind_addr: Some(SocketAddr::from((Ipv4Addr::LOCALHOST, 0))),
+        })
+        .insert_resource(handle.clone())
+        .init_resource::<JsRustBridge>()
+        .add_systems(Startup, start_sensor_feed);
+        app.update();
+
+        let feed_addr = app.world().resource::<SensorFeed>().local_addr;
+        let sender = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        for packet in [SINGLE, "{ malformed", BATCH] {
+            sender.send_to(packet.as_bytes(), feed_addr).unwrap();
+        }
+
+        // The feed grants permission first; the malformed packet is skipped and the
+        // other two yield three sensor events
+        let mut received = Vec::new();
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while received.len() < 4 && Instant::now() < deadline {
+            handle.with(|bridge| received.extend(std::iter::from_fn(|| bridge.pop_event())));
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        assert!(
+            matches!(
+                &received[..],
+                [
+                    BridgeEvent::PermissionStatusChanged(PermissionStatus { granted: true, .. }),
+                    BridgeEvent::DeviceOrientation(_),
+                    BridgeEvent::DeviceOrientation(_),
+                    BridgeEvent::ScreenOrientation(_)
+                ]
+            ),
+            "received {:?}",
+            received
+        );
+
+        // Dropping the App stops the receive thread and frees the port
+        drop(app);
+        assert!(UdpSocket::bind(feed_addr).is_ok());
+    }
+}
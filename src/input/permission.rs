@@ -0,0 +1,184 @@
+use super::{HostEnvironment, InputArbiter, JsRustBridge, TiltInput};
+use bevy::prelude::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Where the player is in the motion permission flow
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PermissionStage {
+    /// Waiting for the host handshake
+    #[default]
+    Unknown,
+    /// The browser grants sensor access without asking
+    NotRequired,
+    /// Explaining why tilt is needed; the next tap asks the browser
+    Prompt,
+    /// The browser dialog is open
+    Requesting,
+    Granted,
+    /// Tilt is unavailable; explaining the fallback controls
+    Denied,
+    /// The player has read the denial notice
+    Dismissed,
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct PermissionFlow {
+    pub stage: PermissionStage,
+    // Set while a tap should trigger the browser request; cleared by the tap handler
+    armed: Arc<AtomicBool>,
+}
+
+impl PermissionFlow {
+    /// Whether the permission screen should cover the game
+    pub fn is_blocking(&self) -> bool {
+        matches!(
+            self.stage,
+            PermissionStage::Prompt | PermissionStage::Requesting | PermissionStage::Denied
+        )
+    }
+
+    pub fn dismiss(&mut self) {
+        if self.stage == PermissionStage::Denied {
+            self.stage = PermissionStage::Dismissed;
+        }
+    }
+
+    fn arm(&mut self) {
+        self.armed.store(true, Ordering::SeqCst);
+    }
+
+    fn is_armed(&self) -> bool {
+        self.armed.load(Ordering::SeqCst)
+    }
+}
+
+/// System advancing the permission flow from the handshake and permission status
+pub fn update_permission_flow(
+    mut flow: ResMut<PermissionFlow>,
+    environment: Res<HostEnvironment>,
+    bridge: Res<JsRustBridge>,
+    mut tilt_input: ResMut<TiltInput>,
+    arbiter: Res<InputArbiter>,
+) {
+    let status = &bridge.permission_status;
+
+    match flow.stage {
+        PermissionStage::Unknown => {
+            let Some(host) = &environment.host else {
+                return;
+            };
+            if host.permission_gesture_required && !status.granted {
+                flow.stage = PermissionStage::Prompt;
+                flow.arm();
+            } else {
+                flow.stage = PermissionStage::NotRequired;
+            }
+        }
+        PermissionStage::Prompt | PermissionStage::Requesting => {
+            if status.granted {
+                info!("Motion permission granted");
+                flow.stage = PermissionStage::Granted;
+            } else if status.requested {
+                // Route the player to controls that work without sensors
+                warn!("Motion permission denied, using {:?}", arbiter.fallback);
+                tilt_input.reset_tilt();
+                tilt_input.set_input_source(arbiter.fallback);
+                flow.stage = PermissionStage::Denied;
+            } else if flow.stage == PermissionStage::Prompt && !flow.is_armed() {
+                flow.stage = PermissionStage::Requesting;
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Startup system listening for taps on the game canvas. iOS only shows the permission
+/// dialog when `requestPermission()` is called inside a user gesture, which Bevy's
+/// buffered input can't provide, so the request is made straight from the DOM handler.
+#[cfg(target_arch = "wasm32")]
+pub fn install_permission_request(
+    flow: Res<PermissionFlow>,
+    bridge: Res<JsRustBridge>,
+    windows: Query<&Window, With<bevy::window::PrimaryWindow>>,
+) {
+    use super::{PermissionStatus, DEFAULT_CANVAS_ID};
+    use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+    use wasm_bindgen_futures::JsFuture;
+
+    /// Call `<class>.requestPermission()` if the browser has it
+    fn request_permission(class: &str) -> Option<js_sys::Promise> {
+        let window = web_sys::window()?;
+        let constructor = js_sys::Reflect::get(&window, &JsValue::from_str(class)).ok()?;
+        let request = js_sys::Reflect::get(&constructor, &JsValue::from_str("requestPermission"))
+            .ok()?
+            .dyn_into::<js_sys::Function>()
+            .ok()?;
+        request.call0(&constructor).ok()?.dyn_into().ok()
+    }
+
+    let canvas_id = windows
+        .get_single()
+        .ok()
+        .and_then(|window| window.canvas.clone())
+        .map(|selector| selector.trim_start_matches('#').to_string())
+        .unwrap_or_else(|| DEFAULT_CANVAS_ID.to_string());
+    let Some(canvas) = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id(&canvas_id))
+    else {
+        warn!(
+            "Canvas #{} not found; motion permission can't be requested",
+            canvas_id
+        );
+        return;
+    };
+
+    let armed = flow.armed.clone();
+    let handle = bridge.handle().clone();
+    let on_tap = Closure::<dyn FnMut(web_sys::Event)>::new(move |_event: web_sys::Event| {
+        if !armed.swap(false, Ordering::SeqCst) {
+            return;
+        }
+
+        // Both requests must start inside the gesture; awaiting them afterwards is fine
+        let orientation = request_permission("DeviceOrientationEvent");
+        let motion = request_permission("DeviceMotionEvent");
+        let handle = handle.clone();
+
+        wasm_bindgen_futures::spawn_local(async move {
+            let granted = match orientation {
+                Some(promise) => JsFuture::from(promise)
+                    .await
+                    .ok()
+                    .and_then(|response| response.as_string())
+                    .is_some_and(|response| response == "granted"),
+                None => true, // Nothing to ask for
+            };
+            if let Some(promise) = motion {
+                let _ = JsFuture::from(promise).await;
+            }
+
+            handle.with(|bridge| {
+                bridge.set_permission_status(PermissionStatus {
+                    granted,
+                    requested: true,
+                    available: true,
+                })
+            });
+        });
+    });
+
+    for event in ["click", "touchend"] {
+        if let Err(err) =
+            canvas.add_event_listener_with_callback(event, on_tap.as_ref().unchecked_ref())
+        {
+            warn!(
+                "Failed to listen for {} on #{}: {:?}",
+                event, canvas_id, err
+            );
+        }
+    }
+    // The listener lives as long as the page
+    on_tap.forget();
+}
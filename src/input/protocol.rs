@@ -0,0 +1,267 @@
+use super::{
+    AccelerationData, BridgeEvent, DeviceMotionData, DeviceOrientationData, RotationRateData,
+};
+use serde::{Deserialize, Serialize};
+
+/// Version of the inbound event protocol spoken by this build
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Oldest page protocol that can still be upgraded
+pub const MIN_PROTOCOL_VERSION: u32 = 1;
+
+/// Values per sample in a typed orientation batch: alpha, beta, gamma, timestamp
+pub const ORIENTATION_STRIDE: usize = 4;
+
+/// Values per sample in a typed motion batch:
+/// accel x, y, z, rate alpha, beta, gamma, interval, timestamp
+pub const MOTION_STRIDE: usize = 8;
+
+/// Many events sent across the wasm boundary in one call
+#[derive(Debug, Clone, Deserialize)]
+pub struct EventBatch {
+    pub version: u32,
+    // Kept as raw values so one malformed entry doesn't reject the whole batch
+    pub events: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchEntryError {
+    pub index: usize,
+    pub error: String,
+}
+
+/// Result of ingesting a batch, returned to the host page as JSON
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BatchReport {
+    pub version: u32, // Protocol version of this build
+    pub accepted: usize,
+    pub rejected: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>, // Set when the batch as a whole was rejected
+    pub errors: Vec<BatchEntryError>,
+}
+
+impl BatchReport {
+    fn rejected(error: String) -> Self {
+        Self {
+            version: PROTOCOL_VERSION,
+            error: Some(error),
+            ..Default::default()
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+/// Rewrite an event from an older protocol version into the current shape.
+/// Add a step here whenever an event's fields change.
+fn upgrade_event(version: u32, event: serde_json::Value) -> serde_json::Value {
+    // No migrations yet: version 1 is both the current and the minimum version
+    debug_assert!(version >= MIN_PROTOCOL_VERSION);
+    event
+}
+
+/// Parse a JSON `EventBatch`, collecting per-entry errors instead of failing outright
+pub fn parse_batch(json: &str) -> (Vec<BridgeEvent>, BatchReport) {
+    match serde_json::from_str(json) {
+        Ok(batch) => ingest_batch(batch),
+        Err(err) => (Vec::new(), BatchReport::rejected(err.to_string())),
+    }
+}
+
+/// Upgrade and parse the entries of an already decoded `EventBatch`
+pub fn ingest_batch(batch: EventBatch) -> (Vec<BridgeEvent>, BatchReport) {
+    if let Err(error) = check_version(batch.version) {
+        return (Vec::new(), BatchReport::rejected(error));
+    }
+
+    let mut events = Vec::with_capacity(batch.events.len());
+    let mut report = BatchReport {
+        version: PROTOCOL_VERSION,
+        ..Default::default()
+    };
+
+    for (index, entry) in batch.events.into_iter().enumerate() {
+        match serde_json::from_value(upgrade_event(batch.version, entry)) {
+            Ok(event) => events.push(event),
+            Err(err) => report.errors.push(BatchEntryError {
+                index,
+                error: err.to_string(),
+            }),
+        }
+    }
+
+    report.accepted = events.len();
+    report.rejected = report.errors.len();
+    (events, report)
+}
+
+/// Unpack a flat typed-array orientation batch (see `ORIENTATION_STRIDE`)
+pub fn parse_orientation_samples(version: u32, samples: &[f64]) -> (Vec<BridgeEvent>, BatchReport) {
+    parse_samples(version, samples, ORIENTATION_STRIDE, |sample| {
+        BridgeEvent::DeviceOrientation(DeviceOrientationData {
+            alpha: sample[0] as f32,
+            beta: sample[1] as f32,
+            gamma: sample[2] as f32,
+            timestamp: sample[3],
+        })
+    })
+}
+
+/// Unpack a flat typed-array motion batch (see `MOTION_STRIDE`)
+pub fn parse_motion_samples(version: u32, samples: &[f64]) -> (Vec<BridgeEvent>, BatchReport) {
+    parse_samples(version, samples, MOTION_STRIDE, |sample| {
+        BridgeEvent::DeviceMotion(DeviceMotionData {
+            acceleration_including_gravity: AccelerationData {
+                x: sample[0] as f32,
+                y: sample[1] as f32,
+                z: sample[2] as f32,
+            },
+            rotation_rate: RotationRateData {
+                alpha: sample[3] as f32,
+                beta: sample[4] as f32,
+                gamma: sample[5] as f32,
+            },
+            interval: sample[6],
+            timestamp: sample[7],
+        })
+    })
+}
+
+fn parse_samples(
+    version: u32,
+    samples: &[f64],
+    stride: usize,
+    to_event: impl Fn(&[f64]) -> BridgeEvent,
+) -> (Vec<BridgeEvent>, BatchReport) {
+    if let Err(error) = check_version(version) {
+        return (Vec::new(), BatchReport::rejected(error));
+    }
+    if samples.len() % stride != 0 {
+        return (
+            Vec::new(),
+            BatchReport::rejected(format!(
+                "expected a multiple of {} values, got {}",
+                stride,
+                samples.len()
+            )),
+        );
+    }
+
+    let mut events = Vec::with_capacity(samples.len() / stride);
+    let mut report = BatchReport {
+        version: PROTOCOL_VERSION,
+        ..Default::default()
+    };
+
+    for (index, sample) in samples.chunks_exact(stride).enumerate() {
+        if sample.iter().all(|value| value.is_finite()) {
+            events.push(to_event(sample));
+        } else {
+            report.errors.push(BatchEntryError {
+                index,
+                error: "non-finite value".to_string(),
+            });
+        }
+    }
+
+    report.accepted = events.len();
+    report.rejected = report.errors.len();
+    (events, report)
+}
+
+fn check_version(version: u32) -> Result<(), String> {
+    if version > PROTOCOL_VERSION {
+        Err(format!(
+            "protocol version {} is newer than supported version {}",
+            version, PROTOCOL_VERSION
+        ))
+    } else if version < MIN_PROTOCOL_VERSION {
+        Err(format!(
+            "protocol version {} is older than minimum version {}",
+            version, MIN_PROTOCOL_VERSION
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_keeps_good_entries_and_reports_bad_ones() {
+        let (events, report) = parse_batch(
+            r#"{"version": 1, "events": [
+                {"type": "DeviceOrientation", "alpha": 0, "beta": 5, "gamma": 10, "timestamp": 1},
+                {"type": "DeviceOrientation", "beta": 5},
+                {"type": "FocusChanged", "focused": false}
+            ]}"#,
+        );
+
+        assert_eq!(events.len(), 2);
+        assert!(matches!(
+            events[1],
+            BridgeEvent::FocusChanged { focused: false }
+        ));
+        assert_eq!((report.accepted, report.rejected), (2, 1));
+        assert_eq!(report.errors[0].index, 1);
+        assert!(report.error.is_none());
+    }
+
+    #[test]
+    fn batch_from_an_unsupported_version_is_rejected() {
+        let json = format!(r#"{{"version": {}, "events": []}}"#, PROTOCOL_VERSION + 1);
+        let (events, report) = parse_batch(&json);
+
+        assert!(events.is_empty());
+        assert!(report.error.is_some_and(|error| error.contains("newer")));
+    }
+
+    #[test]
+    fn malformed_batch_is_rejected_as_a_whole() {
+        let (events, report) = parse_batch("{\"events\": ");
+
+        assert!(events.is_empty());
+        assert!(report.error.is_some());
+    }
+
+    #[test]
+    fn typed_samples_unpack_by_stride() {
+        let samples = [1.0, 2.0, 3.0, 100.0, 4.0, 5.0, 6.0, 116.0];
+        let (events, report) = parse_orientation_samples(PROTOCOL_VERSION, &samples);
+
+        assert_eq!(report.accepted, 2);
+        let BridgeEvent::DeviceOrientation(second) = &events[1] else {
+            panic!("expected an orientation event, got {:?}", events[1]);
+        };
+        assert_eq!(
+            (second.beta, second.gamma, second.timestamp),
+            (5.0, 6.0, 116.0)
+        );
+    }
+
+    #[test]
+    fn typed_samples_with_a_stride_mismatch_are_rejected() {
+        let samples = [0.0; MOTION_STRIDE + 3];
+        let (events, report) = parse_motion_samples(PROTOCOL_VERSION, &samples);
+
+        assert!(events.is_empty());
+        assert!(report
+            .error
+            .is_some_and(|error| error.contains(&MOTION_STRIDE.to_string())));
+    }
+
+    #[test]
+    fn non_finite_samples_are_skipped() {
+        let samples = [0.0, 0.0, f64::NAN, 1.0, 0.0, 0.0, 0.0, 2.0];
+        let (events, report) = parse_orientation_samples(PROTOCOL_VERSION, &samples);
+
+        assert_eq!(events.len(), 1);
+        assert_eq!((report.accepted, report.rejected), (1, 1));
+        assert_eq!(report.errors[0].index, 0);
+    }
+}
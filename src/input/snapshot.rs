@@ -0,0 +1,187 @@
+use super::{FilterMode, InputSource, JsRustBridge, SensorMode, TiltInput};
+use crate::core::{Block, ControlScheme, Difficulty, GameMode, GameSession, Tower};
+use crate::physics::{GravityManager, GravityProfile, StabilityMargin};
+use crate::{GameScore, GameState};
+use bevy::prelude::*;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ScoreView {
+    pub current: u32,
+    pub best: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StateView {
+    pub state: String,
+    pub mode: GameMode,
+    pub control_scheme: ControlScheme,
+    pub difficulty: Difficulty,
+    pub landing_assist: bool,
+    pub seed: u64,
+    pub frame: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BlockView {
+    pub x: f32,
+    pub y: f32,
+    pub rotation: f32, // Radians
+    pub width: f32,
+    pub height: f32,
+    pub settled: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TowerView {
+    pub height: f32,
+    pub block_count: usize,
+    pub settled_count: usize,
+    pub stability_margin: Option<f32>, // Pixels at the weakest level; negative is tipping
+    pub wobble: f32,                   // 0.0 (steady) to 1.0 (about to tip)
+    pub blocks: Vec<BlockView>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InputView {
+    pub source: InputSource,
+    pub enabled: bool,
+    pub tilt: [f32; 2],    // Normalized (-1.0 to 1.0)
+    pub gravity: [f32; 2], // Unit gravity direction
+    pub filtered_beta: f32,
+    pub filtered_gamma: f32,
+    pub screen_angle: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SettingsView {
+    pub sensitivity: f32,
+    pub dead_zone: f32,
+    pub ema_alpha: f32,
+    pub sensor_mode: SensorMode,
+    pub filter_mode: FilterMode,
+    pub gravity_profile: GravityProfile,
+}
+
+/// Per-frame copy of game state that the host page can query synchronously
+#[derive(Debug, Clone, Serialize)]
+pub struct GameSnapshot {
+    pub score: ScoreView,
+    pub state: StateView,
+    pub tower: TowerView,
+    pub input: InputView,
+    pub settings: SettingsView,
+}
+
+impl GameSnapshot {
+    /// Serialize one named view (`score`, `state`, `tower`, `input`, `settings` or `all`)
+    pub fn view_json(&self, view: &str) -> Result<String, String> {
+        let json = match view {
+            "score" => serde_json::to_string(&self.score),
+            "state" => serde_json::to_string(&self.state),
+            "tower" => serde_json::to_string(&self.tower),
+            "input" => serde_json::to_string(&self.input),
+            "settings" => serde_json::to_string(&self.settings),
+            "all" => serde_json::to_string(self),
+            _ => return Err(format!("unknown view '{}'", view)),
+        };
+
+        json.map_err(|err| err.to_string())
+    }
+}
+
+impl InputView {
+    fn new(tilt_input: &TiltInput, gravity: &GravityProfile) -> Self {
+        let tilt = tilt_input.get_normalized_tilt_with(gravity.max_tilt);
+        let gravity = gravity.direction(tilt_input);
+
+        Self {
+            source: tilt_input.input_source,
+            enabled: tilt_input.enabled,
+            tilt: [tilt.x, tilt.y],
+            gravity: [gravity.x, gravity.y],
+            filtered_beta: tilt_input.filtered_beta,
+            filtered_gamma: tilt_input.filtered_gamma,
+            screen_angle: tilt_input.screen_rotation.angle(),
+        }
+    }
+}
+
+impl SettingsView {
+    fn new(tilt_input: &TiltInput, gravity: &GravityProfile) -> Self {
+        Self {
+            sensitivity: tilt_input.sensitivity,
+            dead_zone: tilt_input.dead_zone,
+            ema_alpha: tilt_input.ema_alpha,
+            sensor_mode: tilt_input.sensor_mode,
+            filter_mode: tilt_input.filter_mode,
+            gravity_profile: gravity.clone(),
+        }
+    }
+}
+
+// Bevy system for publishing the query snapshot each frame
+#[allow(clippy::too_many_arguments)]
+pub fn publish_state_snapshot(
+    bridge: Res<JsRustBridge>,
+    score: Option<Res<GameScore>>,
+    state: Res<State<GameState>>,
+    session: Res<GameSession>,
+    tilt_input: Res<TiltInput>,
+    gravity: Option<Res<GravityManager>>,
+    stability: Option<Res<StabilityMargin>>,
+    towers: Query<&Tower>,
+    blocks: Query<(&Block, &Transform)>,
+    mut frame: Local<u64>,
+) {
+    *frame += 1;
+
+    let blocks: Vec<BlockView> = blocks
+        .iter()
+        .map(|(block, transform)| BlockView {
+            x: transform.translation.x,
+            y: transform.translation.y,
+            rotation: transform.rotation.to_euler(EulerRot::ZYX).0,
+            width: block.size.x,
+            height: block.size.y,
+            settled: block.settled,
+        })
+        .collect();
+
+    let profile = gravity
+        .map(|gravity| gravity.profile.clone())
+        .unwrap_or_default();
+
+    let snapshot = GameSnapshot {
+        score: score
+            .map(|score| ScoreView {
+                current: score.current,
+                best: score.best,
+            })
+            .unwrap_or_default(),
+        state: StateView {
+            state: format!("{:?}", state.get()),
+            mode: session.mode,
+            control_scheme: session.control_scheme,
+            difficulty: session.difficulty,
+            landing_assist: session.landing_assist,
+            seed: session.seed,
+            frame: *frame,
+        },
+        tower: TowerView {
+            height: towers.iter().map(|tower| tower.height).fold(0.0, f32::max),
+            block_count: blocks.len(),
+            settled_count: blocks.iter().filter(|block| block.settled).count(),
+            stability_margin: stability
+                .as_ref()
+                .and_then(|stability| stability.weakest)
+                .map(|level| level.margin),
+            wobble: stability.map_or(0.0, |stability| stability.wobble()),
+            blocks,
+        },
+        input: InputView::new(&tilt_input, &profile),
+        settings: SettingsView::new(&tilt_input, &profile),
+    };
+
+    bridge.publish_snapshot(snapshot);
+}
@@ -1,53 +1,262 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 #[derive(Resource)]
 pub struct TiltInput {
     // Raw device data
-    pub beta: f32,  // Front-to-back tilt (-180 to 180 degrees)
-    pub gamma: f32, // Left-to-right tilt (-90 to 90 degrees)
-    pub alpha: f32, // Compass heading (0 to 360 degrees)
+    pub beta: f32,         // Front-to-back tilt (-180 to 180 degrees)
+    pub gamma: f32,        // Left-to-right tilt (-90 to 90 degrees)
+    pub alpha: f32,        // Compass heading (0 to 360 degrees)
+    pub orientation: Quat, // Device tilt rotation built from beta/gamma
+
+    // Raw motion data
+    pub acceleration: Vec3, // accelerationIncludingGravity (m/s², device frame)
+    pub rotation_rate: Vec3, // alpha, beta, gamma rates (degrees per second)
 
     // Processed data
     pub filtered_beta: f32,
     pub filtered_gamma: f32,
+    pub filtered_gravity: Vec2, // In-plane gravity (m/s², screen space)
 
     // Calibration settings
-    pub zero_beta: f32,
-    pub zero_gamma: f32,
+    pub reference_orientation: Quat, // Rotation treated as "level"
     pub sensitivity: f32,
     pub dead_zone: f32,
 
     // Control settings
     pub enabled: bool,
     pub input_source: InputSource,
+    pub sensor_mode: SensorMode,
+    pub screen_rotation: ScreenRotation,
 
     // Smoothing filter state
     pub ema_alpha: f32,
+    pub filter_mode: FilterMode,
+    pub fusion: ComplementaryFilter,
+    pub filter_comparison: FilterComparison,
     pub last_update_time: f64,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum InputSource {
     Device,
     Keyboard,
     Virtual,
+    Gamepad,
+    Mouse,
+}
+
+/// How device sensor data is turned into gravity
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SensorMode {
+    /// Tilt angles from DeviceOrientation events
+    #[default]
+    Orientation,
+    /// Gravity vector taken directly from DeviceMotion accelerometer readings
+    Accelerometer,
+}
+
+/// Smoothing stage that produces `filtered_beta`/`filtered_gamma`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FilterMode {
+    /// Exponential moving average of orientation angles
+    #[default]
+    Ema,
+    /// Gyroscope rates fused with orientation angles
+    Complementary,
+}
+
+/// Complementary filter fusing gyroscope rotation rate (trusted short-term) with
+/// DeviceOrientation angles (trusted long-term, corrects gyro drift).
+///
+/// Rates are integrated as if they were Euler angle derivatives, which holds well
+/// within the ±45° range the game uses.
+#[derive(Debug, Clone)]
+pub struct ComplementaryFilter {
+    pub gyro_weight: f32, // Share of the estimate carried over from gyro integration
+    pub beta: f32,
+    pub gamma: f32,
+    initialized: bool,
+    gyro_updated: bool,
+    last_gyro_time: Option<f64>,
 }
 
+impl Default for ComplementaryFilter {
+    fn default() -> Self {
+        Self {
+            gyro_weight: 0.98,
+            beta: 0.0,
+            gamma: 0.0,
+            initialized: false,
+            gyro_updated: false,
+            last_gyro_time: None,
+        }
+    }
+}
+
+impl ComplementaryFilter {
+    /// Advance the estimate with gyroscope rates (degrees per second)
+    pub fn integrate(&mut self, beta_rate: f32, gamma_rate: f32, timestamp: f64) {
+        if let Some(last_time) = self.last_gyro_time {
+            // Ignore regressions and long gaps (backgrounded tab) instead of jumping
+            let dt = ((timestamp - last_time) / 1000.0) as f32;
+            if dt > 0.0 && dt < 0.1 && self.initialized {
+                self.beta += beta_rate * dt;
+                self.gamma += gamma_rate * dt;
+                self.gyro_updated = true;
+            }
+        }
+        self.last_gyro_time = Some(timestamp);
+    }
+
+    /// Blend the gyro estimate towards measured angles and return the fused angles.
+    /// Without gyroscope samples since the last call, falls back to `fallback_alpha`
+    /// smoothing so devices lacking a gyro still behave like the EMA path.
+    pub fn correct(&mut self, beta: f32, gamma: f32, fallback_alpha: f32) -> (f32, f32) {
+        if !self.initialized {
+            self.beta = beta;
+            self.gamma = gamma;
+            self.initialized = true;
+        } else {
+            let weight = if self.gyro_updated {
+                self.gyro_weight
+            } else {
+                1.0 - fallback_alpha
+            };
+            self.beta = weight * self.beta + (1.0 - weight) * beta;
+            self.gamma = weight * self.gamma + (1.0 - weight) * gamma;
+        }
+        self.gyro_updated = false;
+
+        (self.beta, self.gamma)
+    }
+
+    /// Forget the current estimate (e.g. when tilt is levelled out)
+    pub fn reset(&mut self) {
+        self.initialized = false;
+        self.gyro_updated = false;
+        self.last_gyro_time = None;
+    }
+}
+
+/// Side-by-side statistics of the EMA and fused filter paths, used to A/B them
+#[derive(Debug, Clone, Default)]
+pub struct FilterComparison {
+    pub ema_beta: f32,
+    pub ema_gamma: f32,
+    pub fused_beta: f32,
+    pub fused_gamma: f32,
+    pub ema_jitter: f32,      // Smoothed sample-to-sample change, degrees
+    pub fused_jitter: f32,    // Smoothed sample-to-sample change, degrees
+    pub mean_divergence: f32, // Smoothed |EMA - fused|, degrees
+    pub samples: u32,
+}
+
+impl FilterComparison {
+    const STATS_ALPHA: f32 = 0.05;
+
+    fn record(&mut self, ema: Vec2, fused: Vec2) {
+        let previous_ema = Vec2::new(self.ema_gamma, self.ema_beta);
+        let previous_fused = Vec2::new(self.fused_gamma, self.fused_beta);
+
+        if self.samples > 0 {
+            let a = Self::STATS_ALPHA;
+            self.ema_jitter += a * (ema.distance(previous_ema) - self.ema_jitter);
+            self.fused_jitter += a * (fused.distance(previous_fused) - self.fused_jitter);
+            self.mean_divergence += a * (ema.distance(fused) - self.mean_divergence);
+        }
+
+        self.ema_gamma = ema.x;
+        self.ema_beta = ema.y;
+        self.fused_gamma = fused.x;
+        self.fused_beta = fused.y;
+        self.samples = self.samples.saturating_add(1);
+    }
+}
+
+/// Rotation of the screen content relative to the device's natural orientation,
+/// as reported by `screen.orientation.angle`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScreenRotation {
+    #[default]
+    Portrait, // 0°
+    Landscape,        // 90°
+    PortraitFlipped,  // 180°
+    LandscapeFlipped, // 270°
+}
+
+impl ScreenRotation {
+    /// Snap an orientation angle in degrees to the nearest screen rotation
+    pub fn from_angle(angle: i32) -> Self {
+        match (angle.rem_euclid(360) + 45) / 90 {
+            1 => Self::Landscape,
+            2 => Self::PortraitFlipped,
+            3 => Self::LandscapeFlipped,
+            _ => Self::Portrait,
+        }
+    }
+
+    /// Angle in degrees (0, 90, 180 or 270)
+    pub fn angle(&self) -> i32 {
+        match self {
+            Self::Portrait => 0,
+            Self::Landscape => 90,
+            Self::PortraitFlipped => 180,
+            Self::LandscapeFlipped => 270,
+        }
+    }
+
+    /// Map device-frame tilt angles to screen-space (x, y) tilt
+    pub fn remap_tilt(&self, beta: f32, gamma: f32) -> Vec2 {
+        match self {
+            Self::Portrait => Vec2::new(gamma, beta),
+            Self::Landscape => Vec2::new(beta, -gamma),
+            Self::PortraitFlipped => Vec2::new(-gamma, -beta),
+            Self::LandscapeFlipped => Vec2::new(-beta, gamma),
+        }
+    }
+
+    /// Map an in-plane device-frame vector (x: right edge, y: top edge) to screen space
+    pub fn rotate_vector(&self, v: Vec2) -> Vec2 {
+        match self {
+            Self::Portrait => v,
+            Self::Landscape => Vec2::new(-v.y, v.x),
+            Self::PortraitFlipped => -v,
+            Self::LandscapeFlipped => Vec2::new(v.y, -v.x),
+        }
+    }
+}
+
+/// Minimum in-plane gravity (m/s²) for the accelerometer vector to be trusted.
+/// Below this the device is lying close to flat and the direction is noise.
+const MIN_PLANAR_GRAVITY: f32 = 1.0;
+
+/// Degrees of tilt reported as full deflection by `get_normalized_tilt`
+pub const DEFAULT_MAX_TILT: f32 = 45.0;
+
 impl Default for TiltInput {
     fn default() -> Self {
         Self {
             beta: 0.0,
             gamma: 0.0,
             alpha: 0.0,
+            orientation: Quat::IDENTITY,
+            acceleration: Vec3::ZERO,
+            rotation_rate: Vec3::ZERO,
             filtered_beta: 0.0,
             filtered_gamma: 0.0,
-            zero_beta: 0.0,
-            zero_gamma: 0.0,
+            filtered_gravity: Vec2::ZERO,
+            reference_orientation: Quat::IDENTITY,
             sensitivity: 1.0,
             dead_zone: 2.0, // 2 degrees dead zone
             enabled: false,
             input_source: InputSource::Device,
+            sensor_mode: SensorMode::Orientation,
+            screen_rotation: ScreenRotation::Portrait,
             ema_alpha: 0.3, // Exponential moving average coefficient
+            filter_mode: FilterMode::Ema,
+            fusion: ComplementaryFilter::default(),
+            filter_comparison: FilterComparison::default(),
             last_update_time: 0.0,
         }
     }
@@ -62,21 +271,107 @@ impl TiltInput {
         self.gamma = gamma;
         self.last_update_time = timestamp;
 
-        // Apply calibration (subtract zero points)
-        let calibrated_beta = beta - self.zero_beta;
-        let calibrated_gamma = gamma - self.zero_gamma;
+        // Apply calibration (rotation relative to the reference pose)
+        self.orientation = Self::tilt_rotation(beta, gamma);
+        let (calibrated_beta, calibrated_gamma) = self.get_relative_tilt();
+
+        // Apply dead zone and sensitivity scaling
+        let scaled_beta = self.apply_dead_zone(calibrated_beta) * self.sensitivity;
+        let scaled_gamma = self.apply_dead_zone(calibrated_gamma) * self.sensitivity;
+
+        // EMA path: exponential moving average smoothing
+        let ema_beta = self.ema_filter(self.filter_comparison.ema_beta, scaled_beta);
+        let ema_gamma = self.ema_filter(self.filter_comparison.ema_gamma, scaled_gamma);
+
+        // Fused path: the gyro rates are in the device frame, so fuse the absolute
+        // angles there and apply the calibration to the result
+        let (fused_beta, fused_gamma) = self.fusion.correct(beta, gamma, self.ema_alpha);
+        let (fused_beta, fused_gamma) =
+            self.get_relative_tilt_of(Self::tilt_rotation(fused_beta, fused_gamma));
+        let fused_beta = self.apply_dead_zone(fused_beta) * self.sensitivity;
+        let fused_gamma = self.apply_dead_zone(fused_gamma) * self.sensitivity;
+
+        // Both paths always run so they can be compared while either one is live
+        self.filter_comparison.record(
+            Vec2::new(ema_gamma, ema_beta),
+            Vec2::new(fused_gamma, fused_beta),
+        );
+
+        (self.filtered_beta, self.filtered_gamma) = match self.filter_mode {
+            FilterMode::Ema => (ema_beta, ema_gamma),
+            FilterMode::Complementary => (fused_beta, fused_gamma),
+        };
+    }
+
+    /// Build the device tilt rotation from W3C Euler angles (intrinsic X then Y).
+    ///
+    /// The compass heading (alpha) is left out: it does not change the direction
+    /// of gravity relative to the device, and on most devices its origin is arbitrary.
+    pub fn tilt_rotation(beta: f32, gamma: f32) -> Quat {
+        Quat::from_rotation_x(beta.to_radians()) * Quat::from_rotation_y(gamma.to_radians())
+    }
+
+    /// Gravity direction in the device frame, as if the reference pose were lying flat
+    pub fn get_relative_gravity(&self) -> Vec3 {
+        self.get_relative_gravity_of(self.orientation)
+    }
+
+    fn get_relative_gravity_of(&self, orientation: Quat) -> Vec3 {
+        let relative = self.get_reference_orientation().inverse() * orientation;
+        relative.inverse() * Vec3::NEG_Z
+    }
+
+    /// Pose treated as level for the current source. The calibrated pose belongs to
+    /// the physical device; simulated sources always start from flat.
+    fn get_reference_orientation(&self) -> Quat {
+        match self.input_source {
+            InputSource::Device => self.reference_orientation,
+            _ => Quat::IDENTITY,
+        }
+    }
+
+    /// Tilt relative to the reference pose, expressed as equivalent (beta, gamma) degrees
+    fn get_relative_tilt(&self) -> (f32, f32) {
+        self.get_relative_tilt_of(self.orientation)
+    }
+
+    fn get_relative_tilt_of(&self, orientation: Quat) -> (f32, f32) {
+        let gravity = self.get_relative_gravity_of(orientation);
+        let beta = (-gravity.y).atan2(-gravity.z).to_degrees();
+        let gamma = gravity.x.clamp(-1.0, 1.0).asin().to_degrees();
+        (beta, gamma)
+    }
+
+    /// Update motion data from accelerometer and gyroscope readings
+    pub fn update_motion(&mut self, acceleration: Vec3, rotation_rate: Vec3, timestamp: f64) {
+        self.acceleration = acceleration;
+        self.rotation_rate = rotation_rate;
+        self.last_update_time = timestamp;
+
+        // Propagate the fused estimate with gyro rates around the X (beta) and Y (gamma) axes
+        self.fusion
+            .integrate(rotation_rate.y, rotation_rate.z, timestamp);
+
+        // accelerationIncludingGravity measures the reaction to gravity, so gravity
+        // itself points the opposite way. Only the screen-plane part matters in 2D.
+        let planar_gravity = self
+            .screen_rotation
+            .rotate_vector(Vec2::new(-acceleration.x, -acceleration.y));
 
-        // Apply dead zone
-        let beta_with_deadzone = self.apply_dead_zone(calibrated_beta);
-        let gamma_with_deadzone = self.apply_dead_zone(calibrated_gamma);
+        self.filtered_gravity = self.filtered_gravity.lerp(planar_gravity, self.ema_alpha);
+    }
 
-        // Apply sensitivity scaling
-        let scaled_beta = beta_with_deadzone * self.sensitivity;
-        let scaled_gamma = gamma_with_deadzone * self.sensitivity;
+    /// Whether gravity is currently taken from the accelerometer vector
+    pub fn uses_accelerometer(&self) -> bool {
+        self.input_source == InputSource::Device && self.sensor_mode == SensorMode::Accelerometer
+    }
 
-        // Apply exponential moving average smoothing
-        self.filtered_beta = self.ema_filter(self.filtered_beta, scaled_beta);
-        self.filtered_gamma = self.ema_filter(self.filtered_gamma, scaled_gamma);
+    /// Get accelerometer gravity direction, if the in-plane reading is usable
+    pub fn get_accelerometer_direction(&self) -> Option<Vec2> {
+        if self.filtered_gravity.length() < MIN_PLANAR_GRAVITY {
+            return None;
+        }
+        Some(self.filtered_gravity.normalize())
     }
 
     /// Apply dead zone to input value
@@ -96,47 +391,69 @@ impl TiltInput {
         self.ema_alpha * current + (1.0 - self.ema_alpha) * previous
     }
 
-    /// Get normalized tilt values (-1.0 to 1.0)
+    /// Get normalized tilt values (-1.0 to 1.0) for a 45° range
     pub fn get_normalized_tilt(&self) -> Vec2 {
+        self.get_normalized_tilt_with(DEFAULT_MAX_TILT)
+    }
+
+    /// Get tilt normalized so that `max_tilt` degrees maps to full deflection
+    pub fn get_normalized_tilt_with(&self, max_tilt: f32) -> Vec2 {
         if !self.enabled {
             return Vec2::ZERO;
         }
 
-        // Clamp to reasonable tilt range (-45 to 45 degrees) and normalize
-        let max_tilt = 45.0;
-        let normalized_x = (self.filtered_gamma / max_tilt).clamp(-1.0, 1.0);
-        let normalized_y = (self.filtered_beta / max_tilt).clamp(-1.0, 1.0);
+        if self.uses_accelerometer() {
+            // Express the physical gravity angle as an equivalent left-right tilt
+            let direction = self
+                .get_accelerometer_direction()
+                .unwrap_or(Vec2::new(0.0, -1.0));
+            let angle = direction.x.atan2(-direction.y).to_degrees();
+            return Vec2::new((angle / max_tilt).clamp(-1.0, 1.0), 0.0);
+        }
+
+        let screen_tilt = self.get_screen_space_tilt();
+        let normalized_x = (screen_tilt.x / max_tilt).clamp(-1.0, 1.0);
+        let normalized_y = (screen_tilt.y / max_tilt).clamp(-1.0, 1.0);
 
         Vec2::new(normalized_x, normalized_y)
     }
 
-    /// Get gravity direction vector for Rapier physics
-    pub fn get_gravity_direction(&self) -> Vec2 {
-        if !self.enabled {
-            return Vec2::new(0.0, -1.0); // Default downward gravity
+    /// Get filtered tilt in screen space (x: left-right, y: front-back), in degrees
+    ///
+    /// Filtering and calibration stay in the device frame, so the remap can change
+    /// mid-game without disturbing the reference pose or the smoothing state.
+    /// Simulated sources already produce screen-space values and are not remapped.
+    pub fn get_screen_space_tilt(&self) -> Vec2 {
+        match self.input_source {
+            InputSource::Device => self
+                .screen_rotation
+                .remap_tilt(self.filtered_beta, self.filtered_gamma),
+            _ => Vec2::new(self.filtered_gamma, self.filtered_beta),
         }
-
-        let normalized = self.get_normalized_tilt();
-
-        // Map normalized tilt to gravity direction
-        // X component: left-right tilt (gamma)
-        // Y component: maintain downward bias while allowing forward-back influence
-        let gravity_x = normalized.x;
-        let gravity_y = -1.0 + normalized.y.abs() * 0.3; // Reduce vertical component slightly
-
-        Vec2::new(gravity_x, gravity_y).normalize_or_zero()
     }
 
     /// Calibrate current position as zero point
     pub fn calibrate_zero_point(&mut self) {
-        self.zero_beta = self.beta;
-        self.zero_gamma = self.gamma;
+        // The fused estimate holds absolute angles, so it survives recalibration
+        self.reference_orientation = self.orientation;
         info!(
             "Calibrated zero point: beta={:.2}°, gamma={:.2}°",
-            self.zero_beta, self.zero_gamma
+            self.beta, self.gamma
         );
     }
 
+    /// Level out the current tilt, e.g. when the source producing it goes away
+    pub fn reset_tilt(&mut self) {
+        self.beta = 0.0;
+        self.gamma = 0.0;
+        self.orientation = Quat::IDENTITY;
+        self.filtered_beta = 0.0;
+        self.filtered_gamma = 0.0;
+        self.filtered_gravity = Vec2::ZERO;
+        self.filter_comparison = FilterComparison::default();
+        self.fusion.reset();
+    }
+
     /// Set sensitivity (0.5 to 2.0)
     pub fn set_sensitivity(&mut self, sensitivity: f32) {
         self.sensitivity = sensitivity.clamp(0.5, 2.0);
@@ -153,14 +470,79 @@ impl TiltInput {
         info!("Input source changed to: {:?}", source);
     }
 
+    /// Set how device sensor data is turned into gravity
+    pub fn set_sensor_mode(&mut self, mode: SensorMode) {
+        self.sensor_mode = mode;
+        info!("Sensor mode changed to: {:?}", mode);
+    }
+
+    /// Set smoothing filter used for device tilt
+    pub fn set_filter_mode(&mut self, mode: FilterMode) {
+        self.filter_mode = mode;
+        info!("Filter mode changed to: {:?}", mode);
+    }
+
+    /// Get EMA vs fused filter statistics for A/B comparison
+    pub fn get_filter_report(&self) -> String {
+        let comparison = &self.filter_comparison;
+        format!(
+            "Filter [{:?}] - EMA: β={:.2}° γ={:.2}° jitter={:.3}° | Fused: β={:.2}° γ={:.2}° jitter={:.3}° | Divergence: {:.3}° over {} samples",
+            self.filter_mode,
+            comparison.ema_beta, comparison.ema_gamma, comparison.ema_jitter,
+            comparison.fused_beta, comparison.fused_gamma, comparison.fused_jitter,
+            comparison.mean_divergence, comparison.samples
+        )
+    }
+
+    /// Set screen rotation used to remap device tilt into screen space
+    pub fn set_screen_rotation(&mut self, rotation: ScreenRotation) {
+        if self.screen_rotation != rotation {
+            self.screen_rotation = rotation;
+            info!("Screen rotation changed to: {}°", rotation.angle());
+        }
+    }
+
     /// Get current input status for debugging
     pub fn get_debug_info(&self) -> String {
         format!(
-            "TiltInput - Raw: β={:.2}° γ={:.2}° | Filtered: β={:.2}° γ={:.2}° | Normalized: {:.2},{:.2} | Enabled: {} | Source: {:?}",
+            "TiltInput - Raw: β={:.2}° γ={:.2}° | Filtered: β={:.2}° γ={:.2}° | Normalized: {:.2},{:.2} | Gravity: {:.2},{:.2} | Enabled: {} | Source: {:?} | Mode: {:?} | Screen: {}°",
             self.beta, self.gamma,
             self.filtered_beta, self.filtered_gamma,
             self.get_normalized_tilt().x, self.get_normalized_tilt().y,
-            self.enabled, self.input_source
+            self.filtered_gravity.x, self.filtered_gravity.y,
+            self.enabled, self.input_source, self.sensor_mode, self.screen_rotation.angle()
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calibration_does_not_offset_simulated_sources() {
+        let mut tilt_input = TiltInput {
+            dead_zone: 0.0,
+            ema_alpha: 1.0,
+            ..default()
+        };
+        tilt_input.update_orientation(0.0, 45.0, 0.0, 0.0);
+        tilt_input.calibrate_zero_point();
+        tilt_input.update_orientation(0.0, 45.0, 0.0, 16.0);
+        assert!(tilt_input.filtered_beta.abs() < 1e-3);
+
+        // The arbiter falls back to the keyboard, which starts from neutral
+        tilt_input.set_input_source(InputSource::Keyboard);
+        tilt_input.reset_tilt();
+        tilt_input.update_orientation(0.0, 0.0, 0.0, 32.0);
+        tilt_input.enabled = true;
+        assert!(tilt_input.filtered_beta.abs() < 1e-3);
+        assert!(tilt_input.filtered_gamma.abs() < 1e-3);
+        assert_eq!(tilt_input.get_normalized_tilt(), Vec2::ZERO);
+
+        // Back on the device, the calibrated pose applies again
+        tilt_input.set_input_source(InputSource::Device);
+        tilt_input.update_orientation(0.0, 45.0, 0.0, 48.0);
+        assert!(tilt_input.filtered_beta.abs() < 1e-3);
+    }
+}
@@ -0,0 +1,129 @@
+use super::{InputSource, TiltInput};
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use serde::{Deserialize, Serialize};
+
+/// Regions of the on-screen controls that touches can start in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TouchZone {
+    Joystick,
+    DropArea,
+}
+
+/// Screen-space layout of the on-screen controls in logical pixels (top-left origin)
+#[derive(Resource, Default, Debug, Clone, PartialEq)]
+pub struct TouchControlLayout {
+    pub joystick_center: Vec2,
+    pub joystick_radius: f32,
+    pub drop_area: Rect,
+}
+
+impl TouchControlLayout {
+    /// Size controls relative to the shorter screen edge so they scale across devices
+    pub fn from_window_size(size: Vec2) -> Self {
+        let unit = size.x.min(size.y);
+        let joystick_radius = unit * 0.14;
+        let margin = unit * 0.06;
+
+        Self {
+            joystick_center: Vec2::new(margin + joystick_radius, size.y - margin - joystick_radius),
+            joystick_radius,
+            // Bottom-right quadrant, away from the joystick thumb
+            drop_area: Rect::new(size.x * 0.5, size.y * 0.5, size.x, size.y),
+        }
+    }
+
+    /// Get the control zone at a screen position; the joystick takes priority
+    pub fn zone_at(&self, position: Vec2) -> Option<TouchZone> {
+        // Touches starting this close to the joystick centre grab it
+        if position.distance(self.joystick_center) <= self.joystick_radius * 1.5 {
+            Some(TouchZone::Joystick)
+        } else if self.drop_area.contains(position) {
+            Some(TouchZone::DropArea)
+        } else {
+            None
+        }
+    }
+}
+
+/// State of the on-screen joystick used by `InputSource::Virtual`
+#[derive(Resource)]
+pub struct VirtualJoystick {
+    pub touch_id: Option<u64>,
+    pub offset: Vec2,     // Knob offset (-1.0 to 1.0, y up)
+    pub spring_rate: f32, // Knob return speed when released (per second)
+    pub max_tilt: f32,    // Simulated tilt in degrees at full deflection
+}
+
+impl Default for VirtualJoystick {
+    fn default() -> Self {
+        Self {
+            touch_id: None,
+            offset: Vec2::ZERO,
+            spring_rate: 12.0,
+            max_tilt: 45.0,
+        }
+    }
+}
+
+/// System for keeping touch control layout in sync with the window size
+pub fn update_touch_control_layout(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut layout: ResMut<TouchControlLayout>,
+) {
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+
+    let new_layout = TouchControlLayout::from_window_size(window.size());
+    if *layout != new_layout {
+        *layout = new_layout;
+    }
+}
+
+/// System for virtual joystick input (for touch devices without tilt).
+/// Taps on the drop area are resolved through the action bindings.
+pub fn handle_virtual_tilt_input(
+    touches: Res<Touches>,
+    layout: Res<TouchControlLayout>,
+    mut joystick: ResMut<VirtualJoystick>,
+    mut tilt_input: ResMut<TiltInput>,
+    time: Res<Time>,
+) {
+    if tilt_input.input_source != InputSource::Virtual {
+        joystick.touch_id = None;
+        joystick.offset = Vec2::ZERO;
+        return;
+    }
+
+    // The first new touch inside the joystick zone grabs it
+    if joystick.touch_id.is_none() {
+        joystick.touch_id = touches
+            .iter_just_pressed()
+            .find(|touch| layout.zone_at(touch.position()) == Some(TouchZone::Joystick))
+            .map(|touch| touch.id());
+    }
+
+    // Follow the owning touch only, so other fingers never move the knob
+    match joystick.touch_id.and_then(|id| touches.get_pressed(id)) {
+        Some(touch) => {
+            let delta = (touch.position() - layout.joystick_center) / layout.joystick_radius;
+            joystick.offset = Vec2::new(delta.x, -delta.y).clamp_length_max(1.0);
+        }
+        None => {
+            joystick.touch_id = None;
+
+            // Spring back towards centre
+            let decay = (-joystick.spring_rate * time.delta_seconds()).exp();
+            joystick.offset *= decay;
+        }
+    }
+
+    // Knob right tilts right (gamma), knob up tilts forward (negative beta)
+    let gamma = joystick.offset.x * joystick.max_tilt;
+    let beta = -joystick.offset.y * joystick.max_tilt;
+
+    let current_time = time.elapsed_seconds_f64() * 1000.0;
+    tilt_input.update_orientation(0.0, beta, gamma, current_time);
+    tilt_input.enabled = true;
+}
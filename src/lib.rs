@@ -1,3 +1,4 @@
+use bevy::input::InputSystem;
 use bevy::prelude::*;
 
 pub mod core;
@@ -14,21 +15,114 @@ impl Plugin for TowerTumblerPlugin {
         app.init_state::<GameState>()
             .init_resource::<input::TiltInput>()
             .init_resource::<input::JsRustBridge>()
+            .init_resource::<input::GamepadTiltSettings>()
+            .init_resource::<input::ActiveGamepad>()
+            .init_resource::<input::TouchControlLayout>()
+            .init_resource::<input::VirtualJoystick>()
+            .init_resource::<input::MouseTiltSettings>()
+            .init_resource::<input::ActionBindings>()
+            .init_resource::<input::ActionState>()
+            .init_resource::<input::RebindCapture>()
+            .init_resource::<input::InputArbiter>()
+            .init_resource::<input::SensorDiagnostics>()
+            .init_resource::<input::HostEnvironment>()
+            .init_resource::<input::PermissionFlow>()
+            .init_resource::<GameSession>()
+            .init_resource::<BlockGenerator>()
+            .init_resource::<PauseState>()
+            .insert_resource(ScoreSystem::new())
+            .add_event::<BlockCommand>()
+            .add_event::<BlockSettled>()
+            .add_event::<input::HostCommandRequest>()
+            .add_event::<input::AppFocusChanged>()
+            .add_event::<input::InputSourceChanged>()
             .add_plugins(physics::PhysicsPlugin)
-            .add_systems(Startup, setup_game)
+            .add_systems(
+                Startup,
+                (
+                    setup_game,
+                    input::load_action_bindings,
+                    ui::setup_touch_controls,
+                    ui::setup_controls_menu,
+                    ui::setup_input_notice,
+                    ui::setup_debug_panel,
+                    ui::setup_permission_screen,
+                    ui::setup_pause_screen,
+                    ui::setup_landing_preview,
+                    ui::setup_wobble_meter,
+                ),
+            )
+            .add_systems(
+                PreUpdate,
+                (input::update_action_state, input::capture_rebind)
+                    .chain()
+                    .after(InputSystem),
+            )
+            .add_systems(
+                Update,
+                (
+                    input::detect_gamepad_connections,
+                    input::update_touch_control_layout,
+                    ui::update_touch_controls,
+                    ui::toggle_controls_menu,
+                    ui::handle_controls_menu_buttons,
+                    ui::update_controls_menu_text,
+                    ui::show_input_source_notice,
+                    ui::update_debug_panel,
+                    input::publish_outbound_events,
+                    input::process_bridge_events,
+                    handle_host_commands.after(input::process_bridge_events),
+                    input::update_permission_flow.after(input::process_bridge_events),
+                    ui::update_permission_screen.after(input::update_permission_flow),
+                    ui::handle_permission_buttons,
+                    auto_pause_on_focus_loss.after(input::process_bridge_events),
+                    ui::update_pause_screen,
+                    ui::update_landing_preview,
+                    ui::update_wobble_meter,
+                ),
+            )
+            .add_systems(PostUpdate, input::publish_state_snapshot)
             .add_systems(
                 Update,
                 (
                     handle_game_input,
                     update_game_state,
-                    input::handle_keyboard_input,
-                    input::process_bridge_events,
+                    input::track_input_activity,
+                    input::arbitrate_input_source.after(input::process_bridge_events),
                     input::handle_calibration_input,
                     input::handle_keyboard_tilt_input,
                     input::handle_virtual_tilt_input,
+                    input::handle_gamepad_tilt_input,
+                    input::handle_mouse_tilt_input,
+                    handle_block_commands,
+                    despawn_lost_blocks,
+                    award_stability_bonus.after(physics::analyze_stability),
                 )
                     .run_if(in_state(GameState::Playing)),
-            );
+            )
+            .add_systems(
+                Update,
+                (handle_pause_input, tick_resume_countdown)
+                    .chain()
+                    .run_if(in_state(GameState::Paused)),
+            )
+            .add_systems(OnEnter(GameState::Paused), pause_virtual_time)
+            .add_systems(OnExit(GameState::Paused), resume_virtual_time);
+
+        // Browsers that gate motion sensors need the request made from a tap
+        #[cfg(target_arch = "wasm32")]
+        app.add_systems(Startup, input::install_permission_request);
+
+        // Desktop builds can take tilt from a phone streaming over the LAN.
+        // A `SensorFeedConfig` inserted before the plugin overrides the environment.
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            if !app.world().contains_resource::<input::SensorFeedConfig>() {
+                app.insert_resource(input::SensorFeedConfig::from_env());
+            }
+            app.add_systems(Startup, input::start_sensor_feed)
+                .add_systems(Update, input::forward_window_focus);
+        }
     }
 }
 
@@ -37,6 +131,7 @@ pub enum GameState {
     #[default]
     MainMenu,
     Playing,
+    Paused,
     GameOver,
 }
 
@@ -49,10 +144,21 @@ pub struct GameScore {
     pub best: u32,
 }
 
+/// Centre of the ground the tower is built on
+const GROUND_POSITION: Vec2 = Vec2::new(0.0, -250.0);
+const GROUND_SIZE: Vec2 = Vec2::new(400.0, 20.0);
+/// Gap between the top of the tower and a newly dropped block
+const DROP_CLEARANCE: f32 = 200.0;
+/// Depth below the ground at which a block counts as lost
+const LOST_DEPTH: f32 = 500.0;
+
 fn setup_game(mut commands: Commands) {
     // Initialize game camera
     commands.spawn((Camera2dBundle::default(), GameCamera));
 
+    // Ground for the tower to stand on
+    physics::create_ground(&mut commands, GROUND_POSITION, GROUND_SIZE);
+
     // Initialize game score
     commands.insert_resource(GameScore::default());
 
@@ -76,14 +182,272 @@ fn setup_game(mut commands: Commands) {
 }
 
 fn handle_game_input(
+    actions: Res<input::ActionState>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut pause: ResMut<PauseState>,
+    mut block_commands: EventWriter<BlockCommand>,
+) {
+    if actions.just_pressed(input::Action::Pause) {
+        pause.reason = PauseReason::Player;
+        next_state.set(GameState::Paused);
+    }
+
+    if actions.just_pressed(input::Action::Drop) {
+        block_commands.send(BlockCommand::Drop);
+    }
+    if actions.just_pressed(input::Action::RotateClockwise) {
+        block_commands.send(BlockCommand::RotateClockwise);
+    }
+    if actions.just_pressed(input::Action::RotateCounterClockwise) {
+        block_commands.send(BlockCommand::RotateCounterClockwise);
+    }
+}
+
+// Any key, click, tap or gamepad button starts the resume countdown
+fn handle_pause_input(
     keyboard_input: Res<ButtonInput<KeyCode>>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    touches: Res<Touches>,
+    mut pause: ResMut<PauseState>,
+) {
+    if keyboard_input.get_just_pressed().next().is_some()
+        || mouse_input.get_just_pressed().next().is_some()
+        || gamepad_buttons.get_just_pressed().next().is_some()
+        || touches.any_just_pressed()
+    {
+        pause.request_resume();
+    }
+}
+
+// Counts down in real time, since virtual time is stopped while paused
+fn tick_resume_countdown(
+    mut pause: ResMut<PauseState>,
+    real_time: Res<Time<Real>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let Some(remaining) = pause.countdown else {
+        return;
+    };
+
+    let remaining = remaining - real_time.delta_seconds();
+    if remaining <= 0.0 {
+        next_state.set(GameState::Playing);
+    } else {
+        pause.countdown = Some(remaining);
+    }
+}
+
+// Pause when the page is hidden or the window loses focus, so the tower
+// doesn't collapse while nobody is watching
+fn auto_pause_on_focus_loss(
+    mut focus_changes: EventReader<input::AppFocusChanged>,
+    state: Res<State<GameState>>,
     mut next_state: ResMut<NextState<GameState>>,
+    mut pause: ResMut<PauseState>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::Escape) {
-        next_state.set(GameState::MainMenu);
+    for change in focus_changes.read() {
+        if change.focused {
+            continue;
+        }
+
+        match state.get() {
+            GameState::Playing => {
+                info!("Focus lost, pausing");
+                pause.reason = PauseReason::FocusLost;
+                next_state.set(GameState::Paused);
+            }
+            GameState::Paused => pause.cancel_resume(),
+            _ => {}
+        }
     }
 }
 
+fn pause_virtual_time(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+fn resume_virtual_time(mut time: ResMut<Time<Virtual>>, mut pause: ResMut<PauseState>) {
+    time.unpause();
+    *pause = PauseState::default();
+}
+
+// Run remote control commands from the host page and acknowledge each one
+#[allow(clippy::too_many_arguments)]
+fn handle_host_commands(
+    mut commands: Commands,
+    mut requests: EventReader<input::HostCommandRequest>,
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut session: ResMut<GameSession>,
+    mut generator: ResMut<BlockGenerator>,
+    mut pause: ResMut<PauseState>,
+    mut score: ResMut<GameScore>,
+    mut tilt_input: ResMut<input::TiltInput>,
+    mut gravity: ResMut<physics::GravityManager>,
+    blocks: Query<Entity, With<Block>>,
+    bridge: Res<input::JsRustBridge>,
+) {
+    // Track the pending state so several commands in one frame see each other's effect
+    let mut current = *state.get();
+
+    let clear_run = |commands: &mut Commands, score: &mut GameScore| {
+        for entity in blocks.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        score.current = 0;
+    };
+
+    for request in requests.read() {
+        let result = match &request.command {
+            input::HostCommand::Start {
+                mode,
+                seed,
+                scheme,
+                difficulty,
+                assist,
+            } => {
+                if matches!(current, GameState::Playing | GameState::Paused) {
+                    Err("a run is already in progress; use restart".to_string())
+                } else {
+                    session.mode = *mode;
+                    session.control_scheme =
+                        scheme.unwrap_or_else(|| mode.default_control_scheme());
+                    session.difficulty = *difficulty;
+                    session.landing_assist =
+                        assist.unwrap_or_else(|| mode.landing_assist(*difficulty));
+                    session.seed = seed.unwrap_or_else(rand::random);
+                    clear_run(&mut commands, &mut score);
+                    generator.reseed(session.seed);
+                    Ok(GameState::Playing)
+                }
+            }
+            input::HostCommand::Pause => match current {
+                GameState::Playing => {
+                    pause.reason = PauseReason::Host;
+                    Ok(GameState::Paused)
+                }
+                other => Err(format!("cannot pause while in {:?}", other)),
+            },
+            // Resumes after the usual countdown
+            input::HostCommand::Resume => match current {
+                GameState::Paused => {
+                    pause.request_resume();
+                    Ok(GameState::Paused)
+                }
+                other => Err(format!("cannot resume while in {:?}", other)),
+            },
+            input::HostCommand::Restart => match current {
+                GameState::MainMenu => Err("no run to restart; use start".to_string()),
+                _ => {
+                    clear_run(&mut commands, &mut score);
+                    generator.reseed(session.seed);
+                    Ok(GameState::Playing)
+                }
+            },
+            input::HostCommand::QuitToMenu => match current {
+                GameState::MainMenu => Err("already in the main menu".to_string()),
+                _ => Ok(GameState::MainMenu),
+            },
+            input::HostCommand::SetInputSource { source } => {
+                tilt_input.reset_tilt();
+                tilt_input.set_input_source(*source);
+                Ok(current)
+            }
+            input::HostCommand::Calibrate => {
+                tilt_input.calibrate_zero_point();
+                Ok(current)
+            }
+            input::HostCommand::SetGravityProfile { profile } => {
+                gravity.set_preset(profile).map(|()| current)
+            }
+        };
+
+        match result {
+            Ok(target) => {
+                if target != current {
+                    next_state.set(target);
+                    current = target;
+                }
+                info!("Host command {} ({:?}) done", request.id, request.command);
+                bridge.send_command_result(request.id, Ok(()));
+            }
+            Err(error) => {
+                warn!("Host command {} rejected: {}", request.id, error);
+                bridge.send_command_result(request.id, Err(error));
+            }
+        }
+    }
+}
+
+fn handle_block_commands(
+    mut commands: Commands,
+    mut block_commands: EventReader<BlockCommand>,
+    mut active: Query<&mut Transform, With<ActiveBlock>>,
+    blocks: Query<(&Block, &Transform), Without<ActiveBlock>>,
+    mut generator: ResMut<BlockGenerator>,
+    bridge: Res<input::JsRustBridge>,
+) {
+    // A block spawned this frame isn't in the query until commands are applied
+    let mut dropped = false;
+
+    for command in block_commands.read() {
+        let angle = match command {
+            BlockCommand::RotateClockwise => -std::f32::consts::FRAC_PI_2,
+            BlockCommand::RotateCounterClockwise => std::f32::consts::FRAC_PI_2,
+            BlockCommand::Drop => {
+                // One block in flight at a time; settling is left to the tower
+                if dropped || !active.is_empty() {
+                    debug!("Drop ignored: a block is still falling");
+                    continue;
+                }
+
+                // Blocks fall as soon as they spawn, so dropping spawns one above the tower
+                let top = blocks
+                    .iter()
+                    .map(|(block, transform)| {
+                        transform.translation.y + half_height(block, transform)
+                    })
+                    .fold(GROUND_POSITION.y + GROUND_SIZE.y * 0.5, f32::max);
+                let size = generator.next_size();
+                physics::create_block(
+                    &mut commands,
+                    Vec2::new(GROUND_POSITION.x, top + DROP_CLEARANCE + size.y * 0.5),
+                    size,
+                );
+                dropped = true;
+                bridge.send_audio_cue("drop");
+                bridge.send_haptic(20);
+                continue;
+            }
+        };
+        bridge.send_audio_cue("rotate");
+
+        // Only the block still in flight can be rotated; anything touching the tower
+        // would be turned into its neighbours
+        for mut transform in active.iter_mut() {
+            transform.rotate_z(angle);
+        }
+    }
+}
+
+// Blocks that slid off the ground would otherwise fall forever
+fn despawn_lost_blocks(mut commands: Commands, blocks: Query<(Entity, &Transform), With<Block>>) {
+    for (entity, transform) in blocks.iter() {
+        if transform.translation.y < GROUND_POSITION.y - LOST_DEPTH {
+            info!("Block {:?} fell off the tower", entity);
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// Vertical half-extent of a block at its current rotation
+fn half_height(block: &Block, transform: &Transform) -> f32 {
+    let half = block.size * 0.5;
+    (transform.rotation * Vec3::X).y.abs() * half.x
+        + (transform.rotation * Vec3::Y).y.abs() * half.y
+}
+
 fn update_game_state() {
     // Game state update logic will be implemented here
 }
@@ -1,4 +1,5 @@
-use crate::core::{Block, Ground};
+use super::Platform;
+use crate::core::{ActiveBlock, Block, BlockSettled, Ground};
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
@@ -17,6 +18,9 @@ pub fn create_ground(commands: &mut Commands, position: Vec2, size: Vec2) -> Ent
             RigidBody::Fixed,
             Collider::cuboid(size.x / 2.0, size.y / 2.0),
             Ground,
+            Platform {
+                rest_position: position,
+            },
         ))
         .id()
 }
@@ -35,12 +39,64 @@ pub fn create_block(commands: &mut Commands, position: Vec2, size: Vec2) -> Enti
             },
             RigidBody::Dynamic,
             Collider::cuboid(size.x / 2.0, size.y / 2.0),
+            Velocity::zero(),
+            ReadMassProperties::default(),
             Restitution::coefficient(0.3),
             Friction::coefficient(0.7),
             Block {
                 size,
                 settled: false,
+                rest_time: 0.0,
             },
+            ActiveBlock,
         ))
         .id()
 }
+
+/// System handing the active block over to plain physics once it makes contact
+pub fn detect_landings(
+    mut commands: Commands,
+    rapier_context: Res<RapierContext>,
+    active: Query<Entity, With<ActiveBlock>>,
+) {
+    for entity in active.iter() {
+        let landed = rapier_context
+            .contact_pairs_with(entity)
+            .any(|pair| pair.has_any_active_contact());
+        if landed {
+            commands.entity(entity).remove::<ActiveBlock>();
+        }
+    }
+}
+
+/// Speed below which a block counts as resting, in pixels per second
+const SETTLE_SPEED: f32 = 5.0;
+/// Spin below which a block counts as resting, in radians per second
+const SETTLE_ANGULAR_SPEED: f32 = 0.1;
+/// Seconds a block must rest before it is settled
+const SETTLE_TIME: f32 = 0.5;
+
+/// System marking blocks settled once they have stayed nearly still for a moment
+pub fn settle_blocks(
+    mut blocks: Query<(Entity, &mut Block, &Velocity)>,
+    mut settled: EventWriter<BlockSettled>,
+    time: Res<Time>,
+) {
+    for (entity, mut block, velocity) in blocks.iter_mut() {
+        if block.settled {
+            continue;
+        }
+
+        let resting =
+            velocity.linvel.length() < SETTLE_SPEED && velocity.angvel.abs() < SETTLE_ANGULAR_SPEED;
+        block.rest_time = if resting {
+            block.rest_time + time.delta_seconds()
+        } else {
+            0.0
+        };
+        if block.rest_time >= SETTLE_TIME {
+            block.settled = true;
+            settled.send(BlockSettled { block: entity });
+        }
+    }
+}
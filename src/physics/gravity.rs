@@ -0,0 +1,147 @@
+use crate::input::TiltInput;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Shape of the mapping from normalized tilt to gravity deflection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseCurve {
+    /// Deflection proportional to tilt
+    Linear,
+    /// Soft near level, for fine control
+    Quadratic,
+    /// Very soft near level, ramping up sharply at the limits
+    Expo,
+    /// Quick response around level, easing into the limits
+    SCurve,
+}
+
+/// Steepness of the expo curve
+const EXPO_STEEPNESS: f32 = 3.0;
+
+impl ResponseCurve {
+    /// Map a normalized tilt (-1.0 to 1.0) onto the curve, keeping its sign
+    pub fn apply(&self, tilt: f32) -> f32 {
+        let x = tilt.clamp(-1.0, 1.0);
+        match self {
+            ResponseCurve::Linear => x,
+            ResponseCurve::Quadratic => x * x.abs(),
+            ResponseCurve::Expo => {
+                x.signum() * (EXPO_STEEPNESS * x.abs()).exp_m1() / EXPO_STEEPNESS.exp_m1()
+            }
+            ResponseCurve::SCurve => {
+                // Smoothstep across the whole -1..1 range: steepest at level
+                let t = (x + 1.0) * 0.5;
+                t * t * (3.0 - 2.0 * t) * 2.0 - 1.0
+            }
+        }
+    }
+}
+
+/// How tilt turns into gravity
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GravityProfile {
+    pub name: String,
+    pub max_tilt: f32, // Degrees of device tilt for full deflection
+    pub curve: ResponseCurve,
+    pub max_deflection: f32, // Degrees gravity may swing away from straight down
+    pub magnitude: f32,      // Pixels/s² (100 pixels per meter)
+    pub beta_contribution: f32, // Share of front-back tilt that steepens the swing (0.0 to 1.0)
+}
+
+impl Default for GravityProfile {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+impl GravityProfile {
+    /// Names of the built-in presets, in cycling order
+    pub const PRESETS: [&'static str; 3] = ["gentle", "classic", "arcade"];
+
+    /// Slow, forgiving response for casual play
+    pub fn gentle() -> Self {
+        Self {
+            name: "gentle".to_string(),
+            max_tilt: 50.0,
+            curve: ResponseCurve::Quadratic,
+            max_deflection: 30.0,
+            magnitude: 780.0,
+            beta_contribution: 0.1,
+        }
+    }
+
+    /// The original tuning: linear, 45° tilt for a 45° swing at 9.8 m/s²
+    pub fn classic() -> Self {
+        Self {
+            name: "classic".to_string(),
+            max_tilt: 45.0,
+            curve: ResponseCurve::Linear,
+            max_deflection: 45.0,
+            magnitude: 980.0, // 9.8 m/s² in pixels/s² (100 pixels per meter)
+            beta_contribution: 0.3,
+        }
+    }
+
+    /// Twitchy response and heavy blocks
+    pub fn arcade() -> Self {
+        Self {
+            name: "arcade".to_string(),
+            max_tilt: 30.0,
+            curve: ResponseCurve::SCurve,
+            max_deflection: 60.0,
+            magnitude: 1400.0,
+            beta_contribution: 0.5,
+        }
+    }
+
+    /// Look up a built-in preset by name
+    pub fn preset(name: &str) -> Option<Self> {
+        match name {
+            "gentle" => Some(Self::gentle()),
+            "classic" => Some(Self::classic()),
+            "arcade" => Some(Self::arcade()),
+            _ => None,
+        }
+    }
+
+    /// The preset after this one, wrapping around
+    pub fn next_preset(&self) -> Self {
+        let index = Self::PRESETS
+            .iter()
+            .position(|name| *name == self.name)
+            .map_or(0, |index| (index + 1) % Self::PRESETS.len());
+        Self::preset(Self::PRESETS[index]).unwrap_or_default()
+    }
+
+    /// Unit gravity direction for the current tilt
+    pub fn direction(&self, tilt_input: &TiltInput) -> Vec2 {
+        let down = Vec2::new(0.0, -1.0);
+        if !tilt_input.enabled {
+            return down;
+        }
+
+        // Accelerometer mode follows physical gravity one-to-one
+        if tilt_input.uses_accelerometer() {
+            return tilt_input.get_accelerometer_direction().unwrap_or(down);
+        }
+
+        let tilt = tilt_input.get_normalized_tilt_with(self.max_tilt);
+        let max_deflection = self.max_deflection.clamp(0.0, 89.0).to_radians();
+
+        // Left-right tilt swings gravity sideways; front-back tilt weakens the
+        // downward component, which steepens the swing
+        let sideways = self.curve.apply(tilt.x) * max_deflection.tan();
+        let downward = 1.0 - self.beta_contribution * self.curve.apply(tilt.y).abs();
+        let angle = sideways
+            .atan2(downward.max(f32::EPSILON))
+            .clamp(-max_deflection, max_deflection);
+
+        Vec2::new(angle.sin(), -angle.cos())
+    }
+
+    /// Gravity vector for the current tilt
+    pub fn gravity(&self, tilt_input: &TiltInput) -> Vec2 {
+        self.direction(tilt_input) * self.magnitude
+    }
+}
@@ -1,5 +1,15 @@
 pub mod bodies;
+pub mod gravity;
+pub mod platform;
+pub mod prediction;
+pub mod stability;
+pub mod steering;
 pub mod world;
 
 pub use bodies::*;
+pub use gravity::*;
+pub use platform::*;
+pub use prediction::*;
+pub use stability::*;
+pub use steering::*;
 pub use world::*;
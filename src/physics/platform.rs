@@ -0,0 +1,104 @@
+use crate::core::{ControlScheme, GameSession};
+use crate::input::TiltInput;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+/// How the platform responds to tilt under `ControlScheme::TiltPlatform`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PlatformMotion {
+    /// Tilt the platform about its centre
+    #[default]
+    Rotate,
+    /// Slide the platform sideways
+    Slide,
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct PlatformSettings {
+    pub motion: PlatformMotion,
+    pub max_angle: f32,            // Degrees
+    pub max_angular_velocity: f32, // Degrees per second
+    pub max_offset: f32,           // Pixels either side of the rest position
+    pub max_speed: f32,            // Pixels per second
+}
+
+impl Default for PlatformSettings {
+    fn default() -> Self {
+        Self {
+            motion: PlatformMotion::Rotate,
+            max_angle: 15.0,
+            max_angular_velocity: 60.0,
+            max_offset: 150.0,
+            max_speed: 300.0,
+        }
+    }
+}
+
+/// Rest pose of a ground that can become a moving platform
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Platform {
+    pub rest_position: Vec2,
+}
+
+/// System switching grounds between fixed and kinematic when the control scheme changes.
+/// Grounds return to their rest pose either way.
+pub fn apply_control_scheme(
+    session: Res<GameSession>,
+    mut platforms: Query<(&Platform, &mut RigidBody, &mut Transform)>,
+    mut applied: Local<Option<ControlScheme>>,
+) {
+    let scheme = session.control_scheme;
+    if *applied == Some(scheme) {
+        return;
+    }
+    *applied = Some(scheme);
+
+    for (platform, mut body, mut transform) in platforms.iter_mut() {
+        *body = match scheme {
+            ControlScheme::TiltPlatform => RigidBody::KinematicPositionBased,
+            ControlScheme::TiltGravity | ControlScheme::SteerBlock => RigidBody::Fixed,
+        };
+        transform.translation = platform.rest_position.extend(transform.translation.z);
+        transform.rotation = Quat::IDENTITY;
+    }
+    info!("Control scheme: {:?}", scheme);
+}
+
+/// Move `current` towards `target` by at most `max_step`
+pub(crate) fn step_towards(current: f32, target: f32, max_step: f32) -> f32 {
+    current + (target - current).clamp(-max_step, max_step)
+}
+
+/// System moving kinematic platforms towards the pose the tilt asks for,
+/// within the angle, offset and speed limits
+pub fn drive_platform(
+    session: Res<GameSession>,
+    settings: Res<PlatformSettings>,
+    tilt_input: Res<TiltInput>,
+    mut platforms: Query<(&Platform, &mut Transform)>,
+    time: Res<Time>,
+) {
+    if session.control_scheme != ControlScheme::TiltPlatform {
+        return;
+    }
+
+    let tilt = tilt_input.get_normalized_tilt().x;
+    let dt = time.delta_seconds();
+
+    for (platform, mut transform) in platforms.iter_mut() {
+        match settings.motion {
+            PlatformMotion::Rotate => {
+                // Tilting right lowers the right-hand edge (clockwise)
+                let target = -tilt * settings.max_angle.to_radians();
+                let current = transform.rotation.to_euler(EulerRot::ZYX).0;
+                let max_step = settings.max_angular_velocity.to_radians() * dt;
+                transform.rotation = Quat::from_rotation_z(step_towards(current, target, max_step));
+            }
+            PlatformMotion::Slide => {
+                let target = platform.rest_position.x + tilt * settings.max_offset;
+                let max_step = settings.max_speed * dt;
+                transform.translation.x = step_towards(transform.translation.x, target, max_step);
+            }
+        }
+    }
+}
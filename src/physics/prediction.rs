@@ -0,0 +1,100 @@
+use crate::core::{ActiveBlock, Block, GameSession, Ground};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+/// Seconds of flight simulated per shape cast
+const PREDICTION_STEP: f32 = 1.0 / 30.0;
+/// Steps simulated before giving up on finding a landing spot (3 seconds)
+const PREDICTION_STEPS: usize = 90;
+
+/// Where the active block is expected to come to rest
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LandingPose {
+    pub position: Vec2,
+    pub rotation: f32, // Radians
+    pub size: Vec2,
+}
+
+/// Landing assist output for the block still in flight
+#[derive(Resource, Debug, Clone, Default)]
+pub struct LandingPrediction {
+    pub block: Option<Entity>,
+    pub landing: Option<LandingPose>,
+    pub trajectory: Vec<Vec2>, // Block centre after each step, up to the landing
+    pub deviation: Option<f32>, // Pixels from the centre of whatever it lands on; positive is right
+}
+
+impl LandingPrediction {
+    pub fn clear(&mut self) {
+        self.block = None;
+        self.landing = None;
+        self.trajectory.clear();
+        self.deviation = None;
+    }
+}
+
+/// System stepping the active block forward under the current gravity, shape-casting
+/// each step against the world until it hits something
+#[allow(clippy::type_complexity)]
+pub fn predict_landing(
+    session: Res<GameSession>,
+    rapier_config: Res<RapierConfiguration>,
+    rapier_context: Res<RapierContext>,
+    blocks: Query<(Entity, &Block, &Transform, &Velocity, &Collider), With<ActiveBlock>>,
+    supports: Query<&Transform, Or<(With<Block>, With<Ground>)>>,
+    mut prediction: ResMut<LandingPrediction>,
+) {
+    prediction.clear();
+    if !session.landing_assist {
+        return;
+    }
+
+    // Normally only one block is active; prefer the highest if the host spawned more
+    let Some((entity, block, transform, velocity, collider)) = blocks
+        .iter()
+        .max_by(|(_, _, a, ..), (_, _, b, ..)| a.translation.y.total_cmp(&b.translation.y))
+    else {
+        return;
+    };
+    prediction.block = Some(entity);
+
+    let gravity = rapier_config.gravity;
+    let rotation = transform.rotation.to_euler(EulerRot::ZYX).0;
+    let filter = QueryFilter::new()
+        .exclude_rigid_body(entity)
+        .exclude_sensors();
+    let mut position = transform.translation.truncate();
+    let mut linvel = velocity.linvel;
+
+    for _ in 0..PREDICTION_STEPS {
+        // Average velocity over the step keeps the arc exact under constant gravity
+        let step_velocity = linvel + gravity * (PREDICTION_STEP * 0.5);
+        let hit = rapier_context.cast_shape(
+            position,
+            rotation,
+            step_velocity,
+            collider,
+            ShapeCastOptions::with_max_time_of_impact(PREDICTION_STEP),
+            filter,
+        );
+
+        if let Some((support, hit)) = hit {
+            position += step_velocity * hit.time_of_impact;
+            prediction.trajectory.push(position);
+            prediction.landing = Some(LandingPose {
+                position,
+                rotation,
+                size: block.size,
+            });
+            prediction.deviation = supports
+                .get(support)
+                .ok()
+                .map(|support| position.x - support.translation.x);
+            return;
+        }
+
+        position += step_velocity * PREDICTION_STEP;
+        linvel += gravity * PREDICTION_STEP;
+        prediction.trajectory.push(position);
+    }
+}
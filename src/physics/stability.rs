@@ -0,0 +1,178 @@
+use crate::core::{Block, Ground};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use std::collections::HashMap;
+
+/// How close one level of the tower is to tipping over
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevelStability {
+    pub block: Entity,
+    pub height: f32, // Along the up axis, for ordering levels
+    pub load: f32,   // Mass of this block plus everything resting on it
+    pub center_of_mass: Vec2,
+    pub support: (f32, f32), // Extent of the contacts underneath, along the lateral axis
+    pub margin: f32,         // Pixels to the nearer support edge; negative is tipping
+}
+
+impl LevelStability {
+    /// Margin as a share of half the support width: 1.0 is centred, 0.0 on the edge
+    pub fn ratio(&self) -> f32 {
+        let half_width = (self.support.1 - self.support.0) * 0.5;
+        if half_width > f32::EPSILON {
+            self.margin / half_width
+        } else if self.margin >= 0.0 {
+            0.0
+        } else {
+            -1.0
+        }
+    }
+}
+
+/// Stability of every settled level, recomputed each frame
+#[derive(Resource, Debug, Clone, Default)]
+pub struct StabilityMargin {
+    pub levels: Vec<LevelStability>, // Lowest first
+    pub weakest: Option<LevelStability>,
+}
+
+impl StabilityMargin {
+    /// How close the weakest level is to tipping, from 0.0 (centred) to 1.0 (on the edge)
+    pub fn wobble(&self) -> f32 {
+        self.weakest
+            .map_or(0.0, |level| (1.0 - level.ratio()).clamp(0.0, 1.0))
+    }
+}
+
+struct Node {
+    entity: Entity,
+    mass: f32,
+    center_of_mass: Vec2,
+    height: f32,
+    support: Vec<f32>, // Contacts underneath, projected onto the lateral axis
+    above: Vec<usize>, // Blocks resting on this one
+}
+
+/// System comparing each settled sub-stack's combined centre of mass against the
+/// contacts holding it up. Levels are measured across the current gravity, so a
+/// tilted world counts as leaning.
+pub fn analyze_stability(
+    rapier_config: Res<RapierConfiguration>,
+    rapier_context: Res<RapierContext>,
+    blocks: Query<(Entity, &Block, &Transform, Option<&ReadMassProperties>)>,
+    grounds: Query<(), With<Ground>>,
+    mut stability: ResMut<StabilityMargin>,
+) {
+    let up = (-rapier_config.gravity).try_normalize().unwrap_or(Vec2::Y);
+    let lateral = Vec2::new(up.y, -up.x);
+
+    let mut nodes: Vec<Node> = Vec::new();
+    let mut index: HashMap<Entity, usize> = HashMap::new();
+    for (entity, block, transform, mass_properties) in blocks.iter() {
+        if !block.settled {
+            continue;
+        }
+
+        // Mass properties arrive a frame after spawning; fall back to the block's area
+        let (mass, center_of_mass) = match mass_properties {
+            Some(properties) if properties.mass > 0.0 => (
+                properties.mass,
+                transform
+                    .transform_point(properties.local_center_of_mass.extend(0.0))
+                    .truncate(),
+            ),
+            _ => (
+                block.size.x * block.size.y,
+                transform.translation.truncate(),
+            ),
+        };
+
+        index.insert(entity, nodes.len());
+        nodes.push(Node {
+            entity,
+            mass,
+            center_of_mass,
+            height: transform.translation.truncate().dot(up),
+            support: Vec::new(),
+            above: Vec::new(),
+        });
+    }
+
+    // Sort each contact into "holds up" or "rests on"
+    for node_index in 0..nodes.len() {
+        let entity = nodes[node_index].entity;
+        for pair in rapier_context.contact_pairs_with(entity) {
+            if !pair.has_any_active_contact() {
+                continue;
+            }
+            let other = if pair.collider1() == entity {
+                pair.collider2()
+            } else {
+                pair.collider1()
+            };
+
+            let below = if grounds.contains(other) {
+                true
+            } else if let Some(&other_index) = index.get(&other) {
+                nodes[other_index].height < nodes[node_index].height
+            } else {
+                false // Blocks still in flight don't hold anything up
+            };
+            if !below {
+                continue;
+            }
+
+            let points: Vec<f32> = pair
+                .manifolds()
+                .flat_map(|manifold| {
+                    manifold
+                        .solver_contacts()
+                        .map(|contact| contact.point().dot(lateral))
+                        .collect::<Vec<_>>()
+                })
+                .collect();
+            nodes[node_index].support.extend(points);
+            if let Some(&other_index) = index.get(&other) {
+                nodes[other_index].above.push(node_index);
+            }
+        }
+    }
+
+    let mut levels: Vec<LevelStability> = nodes
+        .iter()
+        .filter_map(|node| {
+            let low = node.support.iter().copied().reduce(f32::min)?;
+            let high = node.support.iter().copied().reduce(f32::max)?;
+
+            // Gather everything this block carries, each block once
+            let mut carried = vec![false; nodes.len()];
+            let mut stack: Vec<usize> = node.above.clone();
+            let (mut load, mut moment) = (node.mass, node.center_of_mass * node.mass);
+            while let Some(above) = stack.pop() {
+                if std::mem::replace(&mut carried[above], true) {
+                    continue;
+                }
+                load += nodes[above].mass;
+                moment += nodes[above].center_of_mass * nodes[above].mass;
+                stack.extend(nodes[above].above.iter().copied());
+            }
+
+            let center_of_mass = moment / load;
+            let offset = center_of_mass.dot(lateral);
+            Some(LevelStability {
+                block: node.entity,
+                height: node.height,
+                load,
+                center_of_mass,
+                support: (low, high),
+                margin: (offset - low).min(high - offset),
+            })
+        })
+        .collect();
+    levels.sort_by(|a, b| a.height.total_cmp(&b.height));
+
+    stability.weakest = levels
+        .iter()
+        .copied()
+        .min_by(|a, b| a.ratio().total_cmp(&b.ratio()));
+    stability.levels = levels;
+}
@@ -0,0 +1,42 @@
+use super::step_towards;
+use crate::core::{ActiveBlock, ControlScheme, GameSession};
+use crate::input::TiltInput;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+/// How tilt steers the falling block under `ControlScheme::SteerBlock`
+#[derive(Resource, Debug, Clone)]
+pub struct SteeringSettings {
+    pub max_speed: f32,    // Horizontal pixels per second at full tilt
+    pub acceleration: f32, // Pixels/s² the block may change speed by
+}
+
+impl Default for SteeringSettings {
+    fn default() -> Self {
+        Self {
+            max_speed: 250.0,
+            acceleration: 1200.0,
+        }
+    }
+}
+
+/// System easing the horizontal velocity of the active block towards the speed the
+/// tilt asks for. Once it touches the tower it is left to plain gravity.
+pub fn steer_falling_block(
+    session: Res<GameSession>,
+    settings: Res<SteeringSettings>,
+    tilt_input: Res<TiltInput>,
+    mut blocks: Query<&mut Velocity, With<ActiveBlock>>,
+    time: Res<Time>,
+) {
+    if session.control_scheme != ControlScheme::SteerBlock {
+        return;
+    }
+
+    let target = tilt_input.get_normalized_tilt().x * settings.max_speed;
+    let max_step = settings.acceleration * time.delta_seconds();
+
+    for mut velocity in blocks.iter_mut() {
+        velocity.linvel.x = step_towards(velocity.linvel.x, target, max_step);
+    }
+}
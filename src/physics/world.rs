@@ -1,4 +1,11 @@
-use crate::input::TiltInput;
+use super::{
+    analyze_stability, apply_control_scheme, detect_landings, drive_platform, predict_landing,
+    settle_blocks, steer_falling_block, GravityProfile, LandingPrediction, PlatformSettings,
+    StabilityMargin, SteeringSettings,
+};
+use crate::core::{ControlScheme, GameSession};
+use crate::input::{Action, ActionState, TiltInput};
+use crate::GameState;
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
@@ -9,35 +16,95 @@ impl Plugin for PhysicsPlugin {
         app.add_plugins(RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(100.0))
             // .add_plugins(RapierDebugRenderPlugin::default())  // Disabled for WASM compatibility
             .add_systems(Startup, setup_physics_world)
-            .add_systems(Update, update_gravity);
+            .init_resource::<PlatformSettings>()
+            .init_resource::<SteeringSettings>()
+            .init_resource::<LandingPrediction>()
+            .init_resource::<StabilityMargin>()
+            .add_systems(Update, (cycle_gravity_profile, update_gravity).chain())
+            .add_systems(
+                Update,
+                (
+                    (apply_control_scheme, drive_platform).chain(),
+                    (detect_landings, settle_blocks, steer_falling_block).chain(),
+                )
+                    .run_if(in_state(GameState::Playing)),
+            )
+            // These run in every state so the preview and meter clear once the run ends
+            .add_systems(
+                Update,
+                (
+                    predict_landing
+                        .after(update_gravity)
+                        .after(steer_falling_block),
+                    analyze_stability.after(update_gravity).after(settle_blocks),
+                ),
+            )
+            .add_systems(OnEnter(GameState::Paused), pause_physics)
+            .add_systems(OnExit(GameState::Paused), resume_physics);
     }
 }
 
 fn setup_physics_world(mut commands: Commands) {
-    // Initialize physics world with default gravity
-    commands.insert_resource(GravityManager::default());
+    // Initialize physics world with the default gravity profile
+    commands.insert_resource(GravityManager::new());
     info!("Physics world initialized");
 }
 
-#[derive(Resource, Default)]
+fn pause_physics(mut rapier_config: ResMut<RapierConfiguration>) {
+    rapier_config.physics_pipeline_active = false;
+}
+
+fn resume_physics(mut rapier_config: ResMut<RapierConfiguration>) {
+    rapier_config.physics_pipeline_active = true;
+}
+
+#[derive(Resource)]
 pub struct GravityManager {
-    pub base_gravity: f32,
+    pub profile: GravityProfile,
     pub last_gravity_update: f64,
     pub update_interval: f64,
 }
 
+impl Default for GravityManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl GravityManager {
     pub fn new() -> Self {
         Self {
-            base_gravity: 980.0, // 9.8 m/s² in pixels/s² (100 pixels per meter)
+            profile: GravityProfile::default(),
             last_gravity_update: 0.0,
             update_interval: 16.0, // Update every 16ms (~60Hz)
         }
     }
+
+    /// Switch to a built-in preset by name
+    pub fn set_preset(&mut self, name: &str) -> Result<(), String> {
+        let profile = GravityProfile::preset(name).ok_or_else(|| {
+            format!(
+                "unknown gravity profile '{}' (expected one of {})",
+                name,
+                GravityProfile::PRESETS.join(", ")
+            )
+        })?;
+        self.profile = profile;
+        info!("Gravity profile: {}", name);
+        Ok(())
+    }
+}
+
+fn cycle_gravity_profile(actions: Res<ActionState>, mut gravity_manager: ResMut<GravityManager>) {
+    if actions.just_pressed(Action::CycleGravityProfile) {
+        gravity_manager.profile = gravity_manager.profile.next_preset();
+        info!("Gravity profile: {}", gravity_manager.profile.name);
+    }
 }
 
 fn update_gravity(
     tilt_input: Res<TiltInput>,
+    session: Res<GameSession>,
     mut gravity_manager: ResMut<GravityManager>,
     mut rapier_config: ResMut<RapierConfiguration>,
     time: Res<Time>,
@@ -50,11 +117,14 @@ fn update_gravity(
     }
     gravity_manager.last_gravity_update = current_time;
 
-    // Get gravity direction from tilt input
-    let gravity_direction = tilt_input.get_gravity_direction();
-
-    // Apply base gravity magnitude to the direction
-    let gravity_vector = gravity_direction * gravity_manager.base_gravity;
+    // Map tilt through the active profile; the other schemes keep gravity straight down
+    let profile = &gravity_manager.profile;
+    let gravity_vector = match session.control_scheme {
+        ControlScheme::TiltGravity => profile.gravity(&tilt_input),
+        ControlScheme::TiltPlatform | ControlScheme::SteerBlock => {
+            Vec2::new(0.0, -profile.magnitude)
+        }
+    };
 
     // Update Rapier gravity
     rapier_config.gravity = gravity_vector;
@@ -62,8 +132,8 @@ fn update_gravity(
     // Debug log gravity changes (throttled)
     if tilt_input.enabled {
         debug!(
-            "Gravity updated: direction=({:.3}, {:.3}), magnitude={:.1}",
-            gravity_direction.x, gravity_direction.y, gravity_manager.base_gravity
+            "Gravity updated: vector=({:.1}, {:.1}), profile={}",
+            gravity_vector.x, gravity_vector.y, profile.name
         );
     }
 }
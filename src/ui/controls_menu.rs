@@ -0,0 +1,165 @@
+use crate::input::{Action, ActionBindings, ActionState, RebindCapture};
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct ControlsMenu;
+
+#[derive(Component)]
+pub struct BindingRow(pub Action);
+
+#[derive(Component)]
+pub struct ResetBindingsButton;
+
+pub fn setup_controls_menu(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                background_color: Color::srgba(0.1, 0.1, 0.1, 0.9).into(),
+                visibility: Visibility::Hidden,
+                z_index: ZIndex::Global(10),
+                ..default()
+            },
+            ControlsMenu,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Controls - select an action, then press a key or button",
+                TextStyle {
+                    font_size: 24.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ));
+
+            for action in Action::ALL {
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                width: Val::Px(420.0),
+                                padding: UiRect::axes(Val::Px(8.0), Val::Px(2.0)),
+                                margin: UiRect::all(Val::Px(1.0)),
+                                ..default()
+                            },
+                            background_color: Color::srgb(0.2, 0.2, 0.2).into(),
+                            ..default()
+                        },
+                        BindingRow(action),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(
+                            action.label(),
+                            TextStyle {
+                                font_size: 16.0,
+                                color: Color::WHITE,
+                                ..default()
+                            },
+                        ));
+                    });
+            }
+
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            padding: UiRect::all(Val::Px(8.0)),
+                            margin: UiRect::all(Val::Px(10.0)),
+                            ..default()
+                        },
+                        background_color: Color::srgb(0.6, 0.2, 0.2).into(),
+                        ..default()
+                    },
+                    ResetBindingsButton,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Reset to defaults",
+                        TextStyle {
+                            font_size: 18.0,
+                            color: Color::WHITE,
+                            ..default()
+                        },
+                    ));
+                });
+        });
+}
+
+pub fn toggle_controls_menu(
+    actions: Res<ActionState>,
+    mut capture: ResMut<RebindCapture>,
+    mut menu_query: Query<&mut Visibility, With<ControlsMenu>>,
+) {
+    if !actions.just_pressed(Action::ToggleControlsMenu) {
+        return;
+    }
+
+    for mut visibility in menu_query.iter_mut() {
+        *visibility = match *visibility {
+            Visibility::Hidden => Visibility::Inherited,
+            _ => {
+                capture.cancel();
+                Visibility::Hidden
+            }
+        };
+    }
+}
+
+pub fn handle_controls_menu_buttons(
+    row_query: Query<(&Interaction, &BindingRow), Changed<Interaction>>,
+    reset_query: Query<&Interaction, (Changed<Interaction>, With<ResetBindingsButton>)>,
+    mut capture: ResMut<RebindCapture>,
+    mut bindings: ResMut<ActionBindings>,
+) {
+    for (interaction, row) in row_query.iter() {
+        if *interaction == Interaction::Pressed {
+            capture.begin(row.0);
+        }
+    }
+
+    for interaction in reset_query.iter() {
+        if *interaction == Interaction::Pressed {
+            capture.cancel();
+            bindings.reset_to_defaults();
+            bindings.save();
+        }
+    }
+}
+
+pub fn update_controls_menu_text(
+    bindings: Res<ActionBindings>,
+    capture: Res<RebindCapture>,
+    row_query: Query<(&BindingRow, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    if !bindings.is_changed() && !capture.is_changed() {
+        return;
+    }
+
+    for (row, children) in row_query.iter() {
+        let label = if capture.action == Some(row.0) {
+            format!("{}: press a key or button (Esc cancels)", row.0.label())
+        } else {
+            let keys: Vec<String> = bindings
+                .bindings_for(row.0)
+                .iter()
+                .map(|binding| binding.label())
+                .collect();
+            format!("{}: {}", row.0.label(), keys.join(", "))
+        };
+
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(*child) {
+                text.sections[0].value.clone_from(&label);
+            }
+        }
+    }
+}
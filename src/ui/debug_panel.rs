@@ -0,0 +1,52 @@
+use crate::input::{Action, ActionState, SensorDiagnostics, TiltInput};
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct DebugPanel;
+
+pub fn setup_debug_panel(mut commands: Commands) {
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 14.0,
+                color: Color::srgb(0.6, 1.0, 0.6),
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(10.0),
+            left: Val::Px(10.0),
+            ..default()
+        })
+        .with_background_color(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+        Visibility::Hidden,
+        DebugPanel,
+    ));
+}
+
+pub fn update_debug_panel(
+    actions: Res<ActionState>,
+    diagnostics: Res<SensorDiagnostics>,
+    tilt_input: Res<TiltInput>,
+    mut query: Query<(&mut Text, &mut Visibility), With<DebugPanel>>,
+) {
+    for (mut text, mut visibility) in query.iter_mut() {
+        if actions.just_pressed(Action::ToggleDiagnostics) {
+            *visibility = match *visibility {
+                Visibility::Hidden => Visibility::Inherited,
+                _ => Visibility::Hidden,
+            };
+        }
+
+        if *visibility != Visibility::Hidden {
+            text.sections[0].value = format!(
+                "{}\n{}\n{}",
+                diagnostics.get_report(),
+                tilt_input.get_debug_info(),
+                tilt_input.get_filter_report()
+            );
+        }
+    }
+}
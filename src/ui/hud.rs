@@ -1,9 +1,17 @@
+use crate::input::{InputSourceChanged, SourceChangeReason};
+use crate::physics::StabilityMargin;
 use crate::GameScore;
 use bevy::prelude::*;
 
 #[derive(Component)]
 pub struct ScoreText;
 
+/// Transient message shown when the input source changes
+#[derive(Component, Default)]
+pub struct InputNotice {
+    pub remaining: f32, // Seconds left on screen
+}
+
 pub fn setup_hud(mut commands: Commands) {
     commands.spawn((
         TextBundle::from_section(
@@ -31,3 +39,155 @@ pub fn update_score_display(score: Res<GameScore>, mut query: Query<&mut Text, W
         }
     }
 }
+
+/// Bar that fills as the tower's weakest level nears its tipping point
+#[derive(Component)]
+pub struct WobbleMeter;
+
+#[derive(Component)]
+pub struct WobbleMeterFill;
+
+#[derive(Component)]
+pub struct WobbleWarning;
+
+/// Wobble above which the meter warns of a collapse
+const WOBBLE_WARNING: f32 = 0.8;
+
+pub fn setup_wobble_meter(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(78.0),
+                    left: Val::Px(10.0),
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(8.0),
+                    ..default()
+                },
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            WobbleMeter,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Px(120.0),
+                        height: Val::Px(10.0),
+                        ..default()
+                    },
+                    background_color: Color::srgba(1.0, 1.0, 1.0, 0.2).into(),
+                    border_radius: BorderRadius::all(Val::Px(5.0)),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        NodeBundle {
+                            style: Style {
+                                width: Val::Percent(0.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            border_radius: BorderRadius::all(Val::Px(5.0)),
+                            ..default()
+                        },
+                        WobbleMeterFill,
+                    ));
+                });
+
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 18.0,
+                        color: Color::srgb(1.0, 0.3, 0.3),
+                        ..default()
+                    },
+                ),
+                WobbleWarning,
+            ));
+        });
+}
+
+pub fn update_wobble_meter(
+    stability: Res<StabilityMargin>,
+    mut meter_query: Query<&mut Visibility, With<WobbleMeter>>,
+    mut fill_query: Query<(&mut Style, &mut BackgroundColor), With<WobbleMeterFill>>,
+    mut text_query: Query<&mut Text, With<WobbleWarning>>,
+) {
+    if !stability.is_changed() {
+        return;
+    }
+
+    for mut visibility in meter_query.iter_mut() {
+        *visibility = if stability.weakest.is_some() {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+
+    // Green when steady, through yellow, to red at the tipping point
+    let wobble = stability.wobble();
+    for (mut style, mut color) in fill_query.iter_mut() {
+        style.width = Val::Percent(wobble * 100.0);
+        *color = Color::srgb((wobble * 2.0).min(1.0), (2.0 - wobble * 2.0).min(1.0), 0.2).into();
+    }
+
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = if wobble >= WOBBLE_WARNING {
+            "Wobbling!".to_string()
+        } else {
+            String::new()
+        };
+    }
+}
+
+pub fn setup_input_notice(mut commands: Commands) {
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 22.0,
+                color: Color::srgb(1.0, 0.85, 0.3),
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            right: Val::Px(10.0),
+            ..default()
+        }),
+        InputNotice::default(),
+    ));
+}
+
+pub fn show_input_source_notice(
+    mut source_changes: EventReader<InputSourceChanged>,
+    mut query: Query<(&mut Text, &mut InputNotice)>,
+    time: Res<Time>,
+) {
+    let latest = source_changes.read().last().copied();
+
+    for (mut text, mut notice) in query.iter_mut() {
+        if let Some(change) = latest {
+            text.sections[0].value = match change.reason {
+                SourceChangeReason::Stale => {
+                    format!("{:?} input lost - using {:?}", change.from, change.to)
+                }
+                SourceChangeReason::Resumed => format!("{:?} input resumed", change.to),
+                SourceChangeReason::Selected => format!("Input: {:?}", change.to),
+            };
+            notice.remaining = 3.0;
+        } else if notice.remaining > 0.0 {
+            notice.remaining -= time.delta_seconds();
+            if notice.remaining <= 0.0 {
+                text.sections[0].value.clear();
+            }
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use crate::physics::LandingPrediction;
+use bevy::prelude::*;
+
+/// Dots drawn along the predicted path
+const TRAJECTORY_DOTS: usize = 24;
+/// Deviation in pixels still drawn as on target
+const ON_TARGET_DEVIATION: f32 = 5.0;
+
+/// Translucent copy of the active block at its predicted landing spot
+#[derive(Component)]
+pub struct LandingGhost;
+
+#[derive(Component)]
+pub struct TrajectoryDot {
+    pub index: usize,
+}
+
+#[derive(Component)]
+pub struct DeviationText;
+
+pub fn setup_landing_preview(mut commands: Commands) {
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::srgba(0.8, 0.4, 0.2, 0.35),
+                ..default()
+            },
+            transform: Transform::from_xyz(0.0, 0.0, -0.5),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        LandingGhost,
+    ));
+
+    for index in 0..TRAJECTORY_DOTS {
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::srgba(1.0, 1.0, 1.0, 0.5),
+                    custom_size: Some(Vec2::splat(4.0)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, 0.0, -0.4),
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            TrajectoryDot { index },
+        ));
+    }
+
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 20.0,
+                color: Color::WHITE,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(48.0),
+            left: Val::Px(10.0),
+            ..default()
+        }),
+        DeviationText,
+    ));
+}
+
+#[allow(clippy::type_complexity)]
+pub fn update_landing_preview(
+    prediction: Res<LandingPrediction>,
+    mut ghost_query: Query<
+        (&mut Transform, &mut Sprite, &mut Visibility),
+        (With<LandingGhost>, Without<TrajectoryDot>),
+    >,
+    mut dot_query: Query<(&TrajectoryDot, &mut Transform, &mut Visibility)>,
+    mut text_query: Query<
+        (&mut Text, &mut Visibility),
+        (
+            With<DeviationText>,
+            Without<LandingGhost>,
+            Without<TrajectoryDot>,
+        ),
+    >,
+) {
+    if !prediction.is_changed() {
+        return;
+    }
+
+    for (mut transform, mut sprite, mut visibility) in ghost_query.iter_mut() {
+        match prediction.landing {
+            Some(pose) => {
+                transform.translation = pose.position.extend(transform.translation.z);
+                transform.rotation = Quat::from_rotation_z(pose.rotation);
+                sprite.custom_size = Some(pose.size);
+                *visibility = Visibility::Inherited;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+
+    // Spread the dots evenly over the path, however long it is
+    let path = &prediction.trajectory;
+    let stride = path.len().div_ceil(TRAJECTORY_DOTS).max(1);
+    for (dot, mut transform, mut visibility) in dot_query.iter_mut() {
+        match path.get(dot.index * stride) {
+            Some(point) => {
+                transform.translation = point.extend(transform.translation.z);
+                *visibility = Visibility::Inherited;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+
+    for (mut text, mut visibility) in text_query.iter_mut() {
+        match prediction.deviation {
+            Some(deviation) => {
+                let section = &mut text.sections[0];
+                section.value = format!("Landing offset: {:+.0} px", deviation);
+                section.style.color = if deviation.abs() <= ON_TARGET_DEVIATION {
+                    Color::srgb(0.3, 0.9, 0.4)
+                } else {
+                    Color::WHITE
+                };
+                *visibility = Visibility::Inherited;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+}
@@ -1,5 +1,17 @@
+pub mod controls_menu;
+pub mod debug_panel;
 pub mod hud;
+pub mod landing_preview;
 pub mod menu;
+pub mod pause_screen;
+pub mod permission_screen;
+pub mod touch_controls;
 
+pub use controls_menu::*;
+pub use debug_panel::*;
 pub use hud::*;
+pub use landing_preview::*;
 pub use menu::*;
+pub use pause_screen::*;
+pub use permission_screen::*;
+pub use touch_controls::*;
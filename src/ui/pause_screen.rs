@@ -0,0 +1,80 @@
+use crate::{GameState, PauseReason, PauseState};
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct PauseScreen;
+
+#[derive(Component)]
+pub struct PauseMessage;
+
+pub fn setup_pause_screen(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                background_color: Color::srgba(0.0, 0.0, 0.0, 0.6).into(),
+                visibility: Visibility::Hidden,
+                z_index: ZIndex::Global(5),
+                ..default()
+            },
+            PauseScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 32.0,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                )
+                .with_text_justify(JustifyText::Center),
+                PauseMessage,
+            ));
+        });
+}
+
+pub fn update_pause_screen(
+    state: Res<State<GameState>>,
+    pause: Res<PauseState>,
+    mut screen_query: Query<&mut Visibility, With<PauseScreen>>,
+    mut text_query: Query<&mut Text, With<PauseMessage>>,
+) {
+    if !state.is_changed() && !pause.is_changed() {
+        return;
+    }
+
+    let paused = *state.get() == GameState::Paused;
+    for mut visibility in screen_query.iter_mut() {
+        *visibility = if paused {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+    if !paused {
+        return;
+    }
+
+    let message = match pause.countdown {
+        Some(remaining) => format!("Resuming in {}", remaining.ceil() as u32),
+        None => {
+            let title = match pause.reason {
+                PauseReason::FocusLost => "Paused while you were away",
+                _ => "Paused",
+            };
+            format!("{}\n\nTap or press any key to resume", title)
+        }
+    };
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value.clone_from(&message);
+    }
+}
@@ -0,0 +1,149 @@
+use crate::input::{InputSource, PermissionFlow, PermissionStage, TiltInput};
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct PermissionScreen;
+
+#[derive(Component)]
+pub struct PermissionMessage;
+
+#[derive(Component)]
+pub struct PermissionContinueButton;
+
+pub fn setup_permission_screen(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(24.0)),
+                    ..default()
+                },
+                background_color: Color::srgba(0.0, 0.0, 0.0, 0.85).into(),
+                visibility: Visibility::Hidden,
+                z_index: ZIndex::Global(20),
+                ..default()
+            },
+            PermissionScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 24.0,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                )
+                .with_text_justify(JustifyText::Center)
+                .with_style(Style {
+                    max_width: Val::Px(420.0),
+                    ..default()
+                }),
+                PermissionMessage,
+            ));
+
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            padding: UiRect::axes(Val::Px(24.0), Val::Px(12.0)),
+                            margin: UiRect::top(Val::Px(20.0)),
+                            ..default()
+                        },
+                        background_color: Color::srgb(0.3, 0.69, 0.31).into(),
+                        border_radius: BorderRadius::all(Val::Px(4.0)),
+                        visibility: Visibility::Hidden,
+                        ..default()
+                    },
+                    PermissionContinueButton,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Continue",
+                        TextStyle {
+                            font_size: 20.0,
+                            color: Color::WHITE,
+                            ..default()
+                        },
+                    ));
+                });
+        });
+}
+
+fn permission_message(stage: PermissionStage, fallback: InputSource) -> String {
+    let controls = match fallback {
+        InputSource::Virtual => "the on-screen joystick",
+        InputSource::Gamepad => "a gamepad",
+        InputSource::Mouse => "the mouse",
+        _ => "the arrow keys",
+    };
+
+    match stage {
+        PermissionStage::Prompt => "Tower Tumbler is steered by tilting your phone.\n\n\
+             Tap anywhere, then choose Allow to share motion sensor data. \
+             It is only used for controls and never leaves your device."
+            .to_string(),
+        PermissionStage::Requesting => "Waiting for motion access...".to_string(),
+        _ => format!(
+            "Motion access was denied, so you'll play with {}.\n\n\
+             To use tilt later, enable Motion & Orientation Access in your browser \
+             settings and reload the page.",
+            controls
+        ),
+    }
+}
+
+pub fn update_permission_screen(
+    flow: Res<PermissionFlow>,
+    tilt_input: Res<TiltInput>,
+    mut screen_query: Query<&mut Visibility, With<PermissionScreen>>,
+    mut button_query: Query<
+        &mut Visibility,
+        (With<PermissionContinueButton>, Without<PermissionScreen>),
+    >,
+    mut text_query: Query<&mut Text, With<PermissionMessage>>,
+) {
+    if !flow.is_changed() {
+        return;
+    }
+
+    let show = |visible: bool| {
+        if visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        }
+    };
+
+    for mut visibility in screen_query.iter_mut() {
+        *visibility = show(flow.is_blocking());
+    }
+    for mut visibility in button_query.iter_mut() {
+        *visibility = show(flow.stage == PermissionStage::Denied);
+    }
+
+    if flow.is_blocking() {
+        let message = permission_message(flow.stage, tilt_input.input_source);
+        for mut text in text_query.iter_mut() {
+            text.sections[0].value.clone_from(&message);
+        }
+    }
+}
+
+pub fn handle_permission_buttons(
+    button_query: Query<&Interaction, (Changed<Interaction>, With<PermissionContinueButton>)>,
+    mut flow: ResMut<PermissionFlow>,
+) {
+    for interaction in button_query.iter() {
+        if *interaction == Interaction::Pressed {
+            flow.dismiss();
+        }
+    }
+}
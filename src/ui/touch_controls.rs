@@ -0,0 +1,155 @@
+use crate::input::{
+    ControlLayout, HostEnvironment, InputSource, TiltInput, TouchControlLayout, VirtualJoystick,
+};
+use crate::GameState;
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct TouchControls;
+
+#[derive(Component)]
+pub struct JoystickBase;
+
+#[derive(Component)]
+pub struct JoystickKnob;
+
+#[derive(Component)]
+pub struct DropArea;
+
+/// Knob diameter relative to the joystick base
+const KNOB_SCALE: f32 = 0.45;
+
+pub fn setup_touch_controls(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            TouchControls,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            ..default()
+                        },
+                        background_color: Color::srgba(1.0, 1.0, 1.0, 0.15).into(),
+                        border_radius: BorderRadius::MAX,
+                        ..default()
+                    },
+                    JoystickBase,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        NodeBundle {
+                            style: Style {
+                                position_type: PositionType::Absolute,
+                                ..default()
+                            },
+                            background_color: Color::srgba(1.0, 1.0, 1.0, 0.5).into(),
+                            border_radius: BorderRadius::MAX,
+                            ..default()
+                        },
+                        JoystickKnob,
+                    ));
+                });
+
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        background_color: Color::srgba(0.2, 0.6, 0.2, 0.15).into(),
+                        ..default()
+                    },
+                    DropArea,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Tap to drop",
+                        TextStyle {
+                            font_size: 24.0,
+                            color: Color::srgba(1.0, 1.0, 1.0, 0.6),
+                            ..default()
+                        },
+                    ));
+                });
+        });
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn update_touch_controls(
+    layout: Res<TouchControlLayout>,
+    joystick: Res<VirtualJoystick>,
+    tilt_input: Res<TiltInput>,
+    environment: Res<HostEnvironment>,
+    state: Res<State<GameState>>,
+    mut root_query: Query<&mut Visibility, With<TouchControls>>,
+    mut joystick_query: Query<&mut Visibility, (With<JoystickBase>, Without<TouchControls>)>,
+    mut styles: ParamSet<(
+        Query<&mut Style, With<JoystickBase>>,
+        Query<&mut Style, With<JoystickKnob>>,
+        Query<&mut Style, With<DropArea>>,
+    )>,
+) {
+    // Touch layouts always get the drop button; the joystick only appears
+    // while the virtual source is driving tilt
+    let joystick_active = tilt_input.input_source == InputSource::Virtual;
+    let active = (joystick_active || environment.layout == ControlLayout::Touch)
+        && *state.get() == GameState::Playing;
+    for mut visibility in joystick_query.iter_mut() {
+        *visibility = if joystick_active {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+    for mut visibility in root_query.iter_mut() {
+        *visibility = if active {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+    if !active {
+        return;
+    }
+
+    let radius = layout.joystick_radius;
+    for mut style in styles.p0().iter_mut() {
+        style.left = Val::Px(layout.joystick_center.x - radius);
+        style.top = Val::Px(layout.joystick_center.y - radius);
+        style.width = Val::Px(radius * 2.0);
+        style.height = Val::Px(radius * 2.0);
+    }
+
+    // Knob is positioned inside the base, offset by the joystick deflection
+    let knob_radius = radius * KNOB_SCALE;
+    for mut style in styles.p1().iter_mut() {
+        style.left = Val::Px(radius - knob_radius + joystick.offset.x * radius);
+        style.top = Val::Px(radius - knob_radius - joystick.offset.y * radius);
+        style.width = Val::Px(knob_radius * 2.0);
+        style.height = Val::Px(knob_radius * 2.0);
+    }
+
+    let area = layout.drop_area;
+    for mut style in styles.p2().iter_mut() {
+        style.left = Val::Px(area.min.x);
+        style.top = Val::Px(area.min.y);
+        style.width = Val::Px(area.width());
+        style.height = Val::Px(area.height());
+    }
+}
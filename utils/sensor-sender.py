@@ -0,0 +1,100 @@
+#!/usr/bin/env python3
+"""
+Stand-in Sensor Sender for Native Builds
+Streams synthetic device orientation/motion over UDP to the desktop game's
+sensor feed, in the same BridgeEvent JSON the web bridge uses.
+
+Start the game with the feed enabled, then run this script:
+    TOWER_TUMBLER_SENSOR_ADDR=7878 cargo run
+    python3 sensor-sender.py --port 7878
+"""
+
+import argparse
+import json
+import math
+import socket
+import time
+
+PROTOCOL_VERSION = 1
+
+
+def orientation_event(t, amplitude, period):
+    # Slow side-to-side sway with a gentle forward nod
+    phase = 2 * math.pi * t / period
+    return {
+        "type": "DeviceOrientation",
+        "alpha": 0.0,
+        "beta": amplitude * 0.3 * math.sin(phase * 0.5),
+        "gamma": amplitude * math.sin(phase),
+        "timestamp": t * 1000.0,
+    }
+
+
+def motion_event(t, amplitude, period, interval_ms):
+    phase = 2 * math.pi * t / period
+    gamma = math.radians(amplitude * math.sin(phase))
+    # Rate of change of gamma in degrees per second
+    rate_gamma = amplitude * (2 * math.pi / period) * math.cos(phase)
+    # accelerationIncludingGravity reads the reaction to gravity: lying flat gives
+    # z = +9.81, and tilting right (positive gamma) swings x negative. The game
+    # negates the reading to recover gravity, so this matches orientation_event.
+    return {
+        "type": "DeviceMotion",
+        "acceleration_including_gravity": {
+            "x": -9.81 * math.sin(gamma),
+            "y": 0.0,
+            "z": 9.81 * math.cos(gamma),
+        },
+        "rotation_rate": {"alpha": 0.0, "beta": 0.0, "gamma": rate_gamma},
+        "interval": interval_ms,
+        "timestamp": t * 1000.0,
+    }
+
+
+def main():
+    parser = argparse.ArgumentParser(description="Send synthetic tilt to the native game")
+    parser.add_argument("--host", default="127.0.0.1")
+    parser.add_argument("--port", type=int, default=7878)
+    parser.add_argument("--rate", type=float, default=60.0, help="samples per second")
+    parser.add_argument("--amplitude", type=float, default=25.0, help="max tilt in degrees")
+    parser.add_argument("--period", type=float, default=4.0, help="seconds per sway")
+    parser.add_argument("--batch", type=int, default=1, help="samples per packet")
+    parser.add_argument("--motion", action="store_true", help="also send DeviceMotion")
+    parser.add_argument("--duration", type=float, default=0.0, help="seconds, 0 = forever")
+    args = parser.parse_args()
+
+    sock = socket.socket(socket.AF_INET, socket.SOCK_DGRAM)
+    target = (args.host, args.port)
+    interval = 1.0 / args.rate
+    start = time.monotonic()
+    pending = []
+    sent = 0
+
+    print(f"Sending tilt to udp://{args.host}:{args.port} at {args.rate:.0f} Hz (Ctrl+C to stop)")
+
+    try:
+        while args.duration <= 0 or time.monotonic() - start < args.duration:
+            t = time.monotonic() - start
+            pending.append(orientation_event(t, args.amplitude, args.period))
+            if args.motion:
+                pending.append(motion_event(t, args.amplitude, args.period, interval * 1000.0))
+
+            if len(pending) >= args.batch * (2 if args.motion else 1):
+                if args.batch == 1:
+                    packets = [json.dumps(event) for event in pending]
+                else:
+                    packets = [json.dumps({"version": PROTOCOL_VERSION, "events": pending})]
+                for packet in packets:
+                    sock.sendto(packet.encode("utf-8"), target)
+                sent += len(pending)
+                pending = []
+
+            time.sleep(interval)
+    except KeyboardInterrupt:
+        pass
+
+    print(f"Sent {sent} events")
+
+
+if __name__ == "__main__":
+    main()